@@ -1,13 +1,13 @@
 #[cfg(not(feature = "library"))]
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::query;
-use crate::{execute, state};
+use crate::{execute, migrations, state};
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response};
 use cw2::set_contract_version;
 
-const CONTRACT_NAME: &str = "crates.io:cw-contract-template";
+const CONTRACT_NAME: &str = "crates.io:cw-gelotto-lottery";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -41,16 +41,49 @@ pub fn execute(
     ExecuteMsg::IssueRefund { round, recipient } => {
       execute::issue_refund(deps, env, info, round, &recipient)
     },
+    ExecuteMsg::ClaimRefund { round } => execute::claim_refund(deps, env, info, round),
+    ExecuteMsg::CancelRound { index } => execute::cancel_round(deps, env, info, index),
+    ExecuteMsg::CancelLottery {} => execute::cancel_lottery(deps, env, info),
+    ExecuteMsg::UpdateConfig { index, config } => {
+      execute::update_config(deps, env, info, index, &config)
+    },
+    ExecuteMsg::AppendRounds { configs, count } => {
+      execute::append_rounds(deps, env, info, &configs, count)
+    },
+    ExecuteMsg::Activate {} => execute::activate(deps, env, info),
+    ExecuteMsg::Pause {} => execute::pause(deps, env, info),
+    ExecuteMsg::Resume {} => execute::resume(deps, env, info),
+    ExecuteMsg::ProposeOwner { owner } => execute::propose_owner(deps, env, info, &owner),
+    ExecuteMsg::AcceptOwner {} => execute::accept_owner(deps, env, info),
+    ExecuteMsg::RenounceOwnership {} => execute::renounce_ownership(deps, env, info),
+    ExecuteMsg::GrantRole { wallet, role } => execute::grant_role(deps, env, info, &wallet, role),
+    ExecuteMsg::RevokeRole { wallet, role } => execute::revoke_role(deps, env, info, &wallet, role),
+    ExecuteMsg::CommitSecret { round, commitment } => {
+      execute::commit_secret(deps, env, info, round, commitment)
+    },
+    ExecuteMsg::RevealSecret { round, secret } => {
+      execute::reveal_secret(deps, env, info, round, &secret)
+    },
+    ExecuteMsg::SubmitBeacon {
+      round,
+      signature,
+      previous_signature,
+    } => execute::submit_beacon(deps, env, info, round, &signature, &previous_signature),
+    ExecuteMsg::ReceiveRandomness { job_id, randomness } => {
+      execute::receive_randomness(deps, env, info, &job_id, &randomness)
+    },
+    ExecuteMsg::Draw { round } => execute::draw(deps, env, info, round),
   }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(
   deps: Deps,
-  _env: Env,
+  env: Env,
   msg: QueryMsg,
 ) -> Result<Binary, ContractError> {
   let result = match msg {
+    QueryMsg::GetLottery {} => to_binary(&query::get_lottery::get_lottery(deps)?),
     QueryMsg::GetRound {
       index,
       players,
@@ -59,6 +92,24 @@ pub fn query(
     } => to_binary(&query::get_round::get_round(
       deps, index, players, winners, orders,
     )?),
+    QueryMsg::GetRoundProgress { index } => to_binary(
+      &query::get_round_progress::get_round_progress(deps, &env, index)?,
+    ),
+    QueryMsg::VerifyDraw { round } => to_binary(&query::verify_draw::verify_draw(deps, round)?),
   }?;
   Ok(result)
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+  deps: DepsMut,
+  env: Env,
+  _msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+  migrations::migrate(deps, &env, CONTRACT_NAME, CONTRACT_VERSION)?;
+  Ok(
+    Response::new()
+      .add_attribute("action", "migrate")
+      .add_attribute("version", CONTRACT_VERSION),
+  )
+}
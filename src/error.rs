@@ -12,6 +12,9 @@ pub enum ContractError {
   #[error("InactiveRound")]
   InactiveRound {},
 
+  #[error("RoundClosed")]
+  RoundClosed {},
+
   #[error("TooManyTickets")]
   TooManyTickets { max_tickets_per_wallet: u32 },
 
@@ -21,6 +24,9 @@ pub enum ContractError {
   #[error("NotActive")]
   NotActive {},
 
+  #[error("Paused")]
+  Paused {},
+
   #[error("NotAuthorized")]
   NotAuthorized {},
 
@@ -47,4 +53,22 @@ pub enum ContractError {
 
   #[error("InvalidSeed")]
   InvalidSeed {},
+
+  #[error("NothingToClaim")]
+  NothingToClaim {},
+
+  #[error("RoundNotReady")]
+  RoundNotReady {},
+
+  #[error("MissingCommitment")]
+  MissingCommitment {},
+
+  #[error("InvalidSecret")]
+  InvalidSecret {},
+
+  #[error("InvalidBeacon")]
+  InvalidBeacon {},
+
+  #[error("MigrationError")]
+  MigrationError { reason: String },
 }
@@ -0,0 +1,30 @@
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, role::Role},
+  state::{ensure_role, load_round, LOTTERY, ROUNDS},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Lottery owner or an operator can activate a pending lottery, starting its
+/// first round.
+pub fn activate(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  // only allow the owner or a wallet with the operator role to activate the lottery
+  ensure_role(deps.storage, &lottery, &info.sender, Role::Operator)?;
+
+  let mut round = load_round(deps.storage, &lottery, None)?;
+  lottery.activate(&env, &mut round)?;
+
+  ROUNDS.save(deps.storage, round.index, &round)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "activate"),
+    attr("round", round.index.to_string()),
+  ]))
+}
@@ -2,10 +2,10 @@ use crate::{
   error::ContractError,
   models::{
     incentive::{Incentive, Reward},
-    lottery::Lottery,
+    lottery::{Lottery, LotteryStatus},
     round::Token,
   },
-  state::{load_round, INCENTIVES, LOTTERY},
+  state::{load_round, INCENTIVES, LOTTERY, ROUNDS},
   utils::{build_cw20_transfer_msg, build_native_send_msg, verify_cw20_funds, verify_native_funds},
 };
 use cosmwasm_std::{attr, CosmosMsg, DepsMut, Env, MessageInfo, Response, SubMsg};
@@ -16,19 +16,35 @@ pub fn add_incentives(
   deps: DepsMut,
   env: Env,
   info: MessageInfo,
-  rewards: &Vec<Reward>,
+  rewards: &[Reward],
 ) -> Result<Response, ContractError> {
-  let lottery: Lottery = LOTTERY.load(deps.storage)?;
-  let round = load_round(deps.storage, &lottery, None)?;
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+  let mut round = load_round(deps.storage, &lottery, None)?;
 
+  // start the lottery if its scheduled start time has passed
+  if lottery.activate_if_scheduled(&env, &mut round)? {
+    ROUNDS.save(deps.storage, round.index, &round)?;
+    LOTTERY.save(deps.storage, &lottery)?;
+  }
+
+  // abort if the lottery is paused
+  if lottery.status == LotteryStatus::Paused {
+    return Err(ContractError::Paused {});
+  }
   // abort if the current round is not active
   if !round.is_active() {
     return Err(ContractError::NotActive {});
   }
   // abort if the incentive's rewards are empty
-  if rewards.len() == 0 {
+  if rewards.is_empty() {
     return Err(ContractError::MissingRewards {});
   }
+  // abort if any reward targets a position other than 1st, 2nd, etc.
+  if rewards.iter().any(|x| x.position == Some(0)) {
+    return Err(ContractError::ValidationError {
+      reason: Some("reward position must be 1 or greater".to_owned()),
+    });
+  }
 
   // verify that the sender actually has sufficient balances of the
   // token types they're adding as incentives. at the same time, prepare
@@ -70,13 +86,13 @@ pub fn add_incentives(
       if let Some(mut incentives) = some_incentives {
         incentives.push(Incentive {
           source: info.sender.clone(),
-          rewards: rewards.clone(),
+          rewards: rewards.to_vec(),
         });
         Ok(incentives)
       } else {
         Ok(vec![Incentive {
           source: info.sender.clone(),
-          rewards: rewards.clone(),
+          rewards: rewards.to_vec(),
         }])
       }
     },
@@ -0,0 +1,29 @@
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, role::Role, round::Config},
+  state::{ensure_role, LOTTERY},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Lottery owner or a config editor can append round configs and raise the
+/// total number of rounds, extending a recurring lottery.
+pub fn append_rounds(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  configs: &[Config],
+  count: Option<u32>,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  // only allow the owner or a wallet with the config editor role to append rounds
+  ensure_role(deps.storage, &lottery, &info.sender, Role::ConfigEditor)?;
+
+  lottery.append_rounds(deps.storage, &env, configs, count)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "append_rounds"),
+    attr("count", lottery.rounds.count.to_string()),
+  ]))
+}
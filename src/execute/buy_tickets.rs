@@ -1,18 +1,19 @@
 use crate::{
   error::ContractError,
   models::{
-    lottery::Lottery,
+    lottery::{Lottery, LotteryStatus},
     player::Player,
-    round::{Config, Round, RoyaltyRecipient, Token, WinnerSelectionMethod},
+    round::{RandomnessSource, Token},
     ticket_order::TicketOrder,
   },
   random::seed,
-  state::{LOTTERY, ORDERS, PLAYERS, ROUNDS, SEED},
+  selection,
+  state::{COMMITMENTS, LOTTERY, ORDERS, PLAYERS, ROUNDS, SEED, TICKETS},
   utils::{verify_cw20_funds, verify_native_funds},
 };
 use cosmwasm_std::{
-  attr, to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, SubMsg,
-  Uint128, WasmMsg,
+  attr, to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Order, Response,
+  SubMsg, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
 
@@ -26,25 +27,56 @@ pub fn buy_tickets(
 ) -> Result<Response, ContractError> {
   let buyer = &info.sender;
   let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
-  let config_index = lottery.get_config_index();
-  let config = lottery.get_config().clone();
   let round_index = lottery.rounds.index;
   let mut round = ROUNDS.load(deps.storage, round_index)?;
+  let config_index = lottery.get_round_config_index(&round);
+  let config = lottery.get_round_config(&round).clone();
 
+  // start the lottery if its scheduled start time has passed
+  lottery.activate_if_scheduled(&env, &mut round)?;
+
+  // abort if the lottery is paused or otherwise not active
+  if lottery.status == LotteryStatus::Paused {
+    return Err(ContractError::Paused {});
+  }
+  if !lottery.is_active() {
+    return Err(ContractError::NotActive {});
+  }
+  // abort if this round has closed and is waiting to be drawn
+  if round.is_closed() {
+    return Err(ContractError::RoundClosed {});
+  }
   // abort if this round is not longer active
   if !round.is_active() {
     return Err(ContractError::InactiveRound {});
   }
 
+  // abort if the round is drawn with a secret that hasn't been committed to
+  let is_commit_reveal = matches!(
+    config.randomness,
+    Some(RandomnessSource::CommitReveal { .. })
+  );
+  if is_commit_reveal && !COMMITMENTS.has(deps.storage, round_index) {
+    return Err(ContractError::MissingCommitment {});
+  }
+
   // unless this round has the initial config, abort if the sender didn't
   // participate in the previous round and this is a tournament
-  if config_index > 0 {
-    if !PLAYERS.has(deps.storage, (round_index - 1, buyer.clone())) {
-      return Err(ContractError::Forbidden {});
-    }
+  if config_index > 0 && !PLAYERS.has(deps.storage, (round_index - 1, buyer.clone())) {
+    return Err(ContractError::Forbidden {});
   }
 
-  let order_index = round.counts.orders;
+  // orders and tickets are numbered after the round's latest order rather
+  // than its counts, which shrink when players are refunded.
+  let (order_index, first_ticket) = match ORDERS
+    .prefix(round_index)
+    .range(deps.storage, None, None, Order::Descending)
+    .next()
+    .transpose()?
+  {
+    Some((last_order_index, last_order)) => (last_order_index + 1, last_order.next_ticket()),
+    None => (0, 1),
+  };
 
   // get or create a player record
   let mut player = match PLAYERS.may_load(deps.storage, (round_index, buyer.clone()))? {
@@ -57,12 +89,12 @@ pub fn buy_tickets(
   };
   // update round metadata
   round.counts.tickets += order_ticket_count;
-  round.counts.drawings = get_updated_winner_count(&config, &round);
   round.counts.orders += 1;
 
   if player.ticket_count == 0 {
     round.counts.wallets += 1;
   }
+  round.counts.drawings = selection::winner_count(&config.selection.method, round.counts.wallets);
   // increment the player's total ticket count in the current round
   player.ticket_count += order_ticket_count;
   player.order_indices.push(order_index);
@@ -77,21 +109,8 @@ pub fn buy_tickets(
     }
   }
 
-  // autosent_royalties is populated only if this buy_tickets execution
-  // results in the completion of the round. background: a claims record is
-  // upserted for all non-autosent royalty recipients; however, for all autosent
-  // recipients, a transfer is performed in this tx.
-  let royalties: Vec<RoyaltyRecipient> = if round.should_end(&config, env.block.time) {
-    if lottery.rounds.index < lottery.rounds.count {
-      lottery.end_round(deps.storage, &env, &info, &config, &mut round)?
-    } else {
-      vec![]
-    }
-  } else {
-    vec![]
-  };
-
-  // persist changes to state
+  // persist the new order before the round can end, so that it's
+  // included in the pool of tickets drawn from.
   SEED.update(deps.storage, |seed| -> Result<String, ContractError> {
     Ok(seed::update(
       &seed,
@@ -102,71 +121,70 @@ pub fn buy_tickets(
     ))
   })?;
   PLAYERS.save(deps.storage, (round_index, buyer.clone()), &player)?;
-  ROUNDS.save(deps.storage, lottery.rounds.index, &round)?;
-  ORDERS.save(
-    deps.storage,
-    (lottery.rounds.index, order_index),
-    &TicketOrder {
-      wallet: info.sender.clone(),
-      ticket_count: order_ticket_count,
-      message,
-      is_public,
-    },
-  )?;
+  let order = TicketOrder {
+    wallet: info.sender.clone(),
+    first_ticket,
+    ticket_count: order_ticket_count,
+    message,
+    is_public,
+  };
+  ORDERS.save(deps.storage, (round_index, order_index), &order)?;
+  TICKETS.save(deps.storage, (round_index, first_ticket), &order_index)?;
+
+  // discourage sniping by pushing back the end of the round
+  round.extend_deadline(&config, env.block.time);
+
+  // settlement_submsgs is populated only if this buy_tickets execution
+  // results in the completion of the round. background: a claims record is
+  // upserted for all non-autosent royalty recipients; however, for all autosent
+  // recipients, a transfer is performed in this tx.
+  let settlement_submsgs: Vec<SubMsg> = if round.should_end(&config, &env.block) {
+    lottery.end_round(deps.storage, &env, &info, &config, &mut round)?
+  } else {
+    vec![]
+  };
+
+  ROUNDS.save(deps.storage, round_index, &round)?;
+  LOTTERY.save(deps.storage, &lottery)?;
 
   // compute total price of the ticket order
   let total_cost = config.ticket_price * Uint128::from(order_ticket_count);
 
   // generate a response with a msg or submsg that performs the required
   // transfer from sender to this contract.
-  Ok(match config.token.clone() {
-    Token::Native { denom } => build_response_with_ibc_transfer(
-      &info,
-      &env,
-      &royalties,
-      &denom,
-      order_ticket_count,
-      total_cost,
-    )?,
+  let response = match config.token.clone() {
+    Token::Native { denom } => {
+      build_response_with_ibc_transfer(&info, &env, &denom, order_ticket_count, total_cost)?
+    },
     Token::Cw20 { address } => build_response_with_cw20_transfer(
       &deps,
       &info,
       &env,
-      &royalties,
       &address,
       order_ticket_count,
       total_cost,
     )?,
-  })
-}
+  };
 
-fn get_updated_winner_count(
-  config: &Config,
-  round: &Round,
-) -> u32 {
-  match config.selection.method.clone() {
-    WinnerSelectionMethod::Percent { pct, max } => {
-      let mut winner_count = (((pct as u32) * round.counts.wallets) / 100u32).max(1);
-      let max_winner_count = max.unwrap_or(0);
-      if max_winner_count > 0 {
-        winner_count = round.counts.drawings.min(max_winner_count);
-      }
-      winner_count
-    },
-    WinnerSelectionMethod::Fixed(split) => split.len() as u32,
-  }
+  Ok(
+    response
+      .add_attributes(vec![
+        attr("first_ticket", order.first_ticket.to_string()),
+        attr("last_ticket", order.last_ticket().to_string()),
+      ])
+      .add_submessages(settlement_submsgs),
+  )
 }
 
 fn build_response_with_cw20_transfer(
   deps: &DepsMut,
   info: &MessageInfo,
   env: &Env,
-  royalties: &Vec<RoyaltyRecipient>,
   cw20_token_address: &Addr,
   ticket_count: u32,
   amount: Uint128,
 ) -> Result<Response, ContractError> {
-  verify_cw20_funds(&deps, &info.sender, amount, &cw20_token_address)?;
+  verify_cw20_funds(deps, &info.sender, amount, cw20_token_address)?;
   // perform CW20 transfer from sender to contract.  note that the cw20
   // token allowance for this contract must be set.
   let execute_msg = WasmMsg::Execute {
@@ -178,25 +196,10 @@ fn build_response_with_cw20_transfer(
     })?,
     funds: vec![],
   };
-  // royalties will only be non-empty if this is the end of the round
-  let mut send_royalty_submsgs: Vec<SubMsg> = Vec::with_capacity(royalties.len());
-  for royalty in royalties.iter() {
-    let royalty_amount = (Uint128::from(royalty.pct) * amount) / Uint128::from(100u8);
-    send_royalty_submsgs.push(SubMsg::new(WasmMsg::Execute {
-      contract_addr: cw20_token_address.clone().into(),
-      msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
-        owner: info.sender.clone().into(),
-        recipient: env.contract.address.clone().into(),
-        amount: royalty_amount,
-      })?,
-      funds: vec![],
-    }));
-  }
 
   Ok(
     Response::new()
       .add_submessage(SubMsg::new(execute_msg))
-      .add_submessages(send_royalty_submsgs)
       .add_attributes(vec![
         attr("action", "buy_tickets"),
         attr("ticket_count", ticket_count.to_string()),
@@ -207,7 +210,6 @@ fn build_response_with_cw20_transfer(
 fn build_response_with_ibc_transfer(
   info: &MessageInfo,
   env: &Env,
-  royalties: &Vec<RoyaltyRecipient>,
   ibc_denom: &String,
   ticket_count: u32,
   amount: Uint128,
@@ -218,22 +220,10 @@ fn build_response_with_ibc_transfer(
     to_address: env.contract.address.clone().into_string(),
     amount: vec![Coin::new(amount.u128(), ibc_denom)],
   });
-  // royalties will only be non-empty if this is the end of the round
-  let send_royalty_messages: Vec<CosmosMsg> = royalties
-    .iter()
-    .map(|x| {
-      let royalty_amount = (Uint128::from(x.pct) * amount) / Uint128::from(100u8);
-      CosmosMsg::Bank(BankMsg::Send {
-        to_address: env.contract.address.clone().into_string(),
-        amount: vec![Coin::new(royalty_amount.u128(), ibc_denom)],
-      })
-    })
-    .collect();
 
   Ok(
     Response::new()
       .add_message(send_payment_message)
-      .add_messages(send_royalty_messages)
       .add_attributes(vec![
         attr("action", "buy_tickets"),
         attr("ticket_count", ticket_count.to_string()),
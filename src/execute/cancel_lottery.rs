@@ -0,0 +1,44 @@
+use crate::{
+  error::ContractError,
+  models::{
+    lottery::{Lottery, LotteryStatus},
+    role::Role,
+  },
+  state::{ensure_role, load_round, LOTTERY, ROUNDS},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Lottery owner or an operator can cancel the lottery as a whole, canceling
+/// its current round so that each player can claim a refund.
+pub fn cancel_lottery(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  // only allow the owner or a wallet with the operator role to cancel the lottery
+  ensure_role(deps.storage, &lottery, &info.sender, Role::Operator)?;
+
+  // abort if the lottery has already completed or been canceled
+  if lottery.is_ended() {
+    return Err(ContractError::NotActive {});
+  }
+
+  let mut round = load_round(deps.storage, &lottery, None)?;
+  let refund_submsgs = lottery.cancel_round(deps.storage, &env, &info, &mut round, false)?;
+  lottery.status = LotteryStatus::Canceled;
+  Lottery::release_jackpot(deps.storage)?;
+
+  ROUNDS.save(deps.storage, round.index, &round)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "cancel_lottery"),
+        attr("round", round.index.to_string()),
+      ])
+      .add_submessages(refund_submsgs),
+  )
+}
@@ -0,0 +1,40 @@
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, role::Role},
+  state::{ensure_role, load_round, LOTTERY, ROUNDS},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Lottery owner or an operator can cancel the current round, after which
+/// each player can claim a refund and the next round begins.
+pub fn cancel_round(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  round_index: u32,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  // only allow the owner or a wallet with the operator role to cancel a round
+  ensure_role(deps.storage, &lottery, &info.sender, Role::Operator)?;
+
+  // only the current round can still be canceled
+  if round_index != lottery.rounds.index {
+    return Err(ContractError::NotActive {});
+  }
+
+  let mut round = load_round(deps.storage, &lottery, Some(round_index))?;
+  let refund_submsgs = lottery.cancel_round(deps.storage, &env, &info, &mut round, true)?;
+
+  ROUNDS.save(deps.storage, round_index, &round)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "cancel_round"),
+        attr("round", round_index.to_string()),
+      ])
+      .add_submessages(refund_submsgs),
+  )
+}
@@ -0,0 +1,15 @@
+use crate::{error::ContractError, models::lottery::Lottery, state::LOTTERY};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+
+use super::issue_refund::refund_player;
+
+/// Players recover the amount they spent on tickets in a canceled round.
+pub fn claim_refund(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  round_index: u32,
+) -> Result<Response, ContractError> {
+  let lottery: Lottery = LOTTERY.load(deps.storage)?;
+  refund_player(deps, &lottery, round_index, &info.sender, "claim_refund")
+}
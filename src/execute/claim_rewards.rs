@@ -1,23 +1,66 @@
 use crate::{
   error::ContractError,
-  models::{lottery::Lottery, round::Token},
-  state::LOTTERY,
-  utils::{build_cw20_transfer_msg, build_native_send_msg},
+  models::{round::Token, winner::Winner},
+  state::{CLAIMS, WINNERS},
+  utils::build_send_submsg,
 };
-use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{attr, Attribute, DepsMut, Env, MessageInfo, Order, Response, SubMsg};
 
 /// Winners and Royalty Recipients claim rewards with this method.
 pub fn claim_rewards(
   deps: DepsMut,
-  env: Env,
+  _env: Env,
   info: MessageInfo,
 ) -> Result<Response, ContractError> {
-  let lottery: Lottery = LOTTERY.load(deps.storage)?;
+  let claim = CLAIMS
+    .may_load(deps.storage, info.sender.clone())?
+    .ok_or(ContractError::NothingToClaim {})?;
 
-  // TODO: transfer winnings to sender
-  // TODO: transfer any additional incentives to sender
-  // TODO: update Claim
+  if claim.is_empty() {
+    return Err(ContractError::NothingToClaim {});
+  }
 
-  let response = Response::new().add_attributes(vec![attr("action", "claim_rewards")]);
-  Ok(response)
+  // mark the winnings in each round included in the claim as claimed
+  for round_index in claim.rounds.iter() {
+    let winnings: Vec<(u32, Winner)> = WINNERS
+      .prefix(*round_index)
+      .range(deps.storage, None, None, Order::Ascending)
+      .filter_map(|entry| entry.ok())
+      .filter(|(_, winner)| winner.wallet == info.sender)
+      .collect();
+    for (position, mut winner) in winnings {
+      winner.amount_claimed = winner.amount_total;
+      WINNERS.save(deps.storage, (*round_index, position), &winner)?;
+    }
+  }
+
+  // the claim is paid out in full, so clear it
+  CLAIMS.remove(deps.storage, info.sender.clone());
+
+  // transfer the claimed balance of each token to the sender
+  let mut attrs: Vec<Attribute> = vec![
+    attr("action", "claim_rewards"),
+    attr("wallet", info.sender.to_string()),
+  ];
+  let mut transfer_submsgs: Vec<SubMsg> = Vec::with_capacity(claim.balances.len());
+  for balance in claim.balances.iter().filter(|x| !x.amount.is_zero()) {
+    attrs.push(attr(
+      "amount",
+      match &balance.token {
+        Token::Native { denom } => format!("{}{}", balance.amount, denom),
+        Token::Cw20 { address } => format!("{}{}", balance.amount, address),
+      },
+    ));
+    transfer_submsgs.push(build_send_submsg(
+      &balance.token,
+      &info.sender,
+      balance.amount,
+    )?);
+  }
+
+  Ok(
+    Response::new()
+      .add_attributes(attrs)
+      .add_submessages(transfer_submsgs),
+  )
 }
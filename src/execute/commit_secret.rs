@@ -0,0 +1,45 @@
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, role::Role, round::RoundStatus},
+  state::{ensure_role, COMMITMENTS, LOTTERY, ROUNDS},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Lottery owner or an operator commits to the hash of a secret for a round
+/// drawn with commit-reveal randomness, before any tickets are bought in it.
+pub fn commit_secret(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  round_index: u32,
+  commitment: String,
+) -> Result<Response, ContractError> {
+  let lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  // only allow the owner or a wallet with the operator role to commit
+  ensure_role(deps.storage, &lottery, &info.sender, Role::Operator)?;
+
+  // abort if the round has already opened to players
+  if round_index < lottery.rounds.index {
+    return Err(ContractError::Forbidden {});
+  }
+  if let Some(round) = ROUNDS.may_load(deps.storage, round_index)? {
+    let is_open = round.is_active() || round.status == RoundStatus::Pending;
+    if round.counts.orders > 0 || !is_open {
+      return Err(ContractError::Forbidden {});
+    }
+  }
+
+  // a commitment can't be replaced once made
+  if COMMITMENTS.has(deps.storage, round_index) {
+    return Err(ContractError::Forbidden {});
+  }
+
+  COMMITMENTS.save(deps.storage, round_index, &commitment)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "commit_secret"),
+    attr("round", round_index.to_string()),
+    attr("commitment", commitment),
+  ]))
+}
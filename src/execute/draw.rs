@@ -0,0 +1,50 @@
+use crate::{
+  error::ContractError,
+  models::lottery::Lottery,
+  random::seed,
+  state::{load_round, LOTTERY, ROUNDS},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Draw the winners of a closed round once the chain has reached its draw
+/// height, mixing data from the current block into the round's seed. Past the
+/// end of its draw window, the round can only be terminated, refunding players.
+pub fn draw(
+  deps: DepsMut,
+  env: Env,
+  _info: MessageInfo,
+  round_index: u32,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+  let mut round = load_round(deps.storage, &lottery, Some(round_index))?;
+  let config = lottery.get_round_config(&round).clone();
+
+  // abort unless the round is waiting on a future block to be drawn
+  let draw_height = match round.draw_height {
+    Some(draw_height) if round.is_closed() => draw_height,
+    _ => return Err(ContractError::NotActive {}),
+  };
+  if env.block.height < draw_height {
+    return Err(ContractError::RoundNotReady {});
+  }
+  if round.is_draw_expired(&config, &env.block) {
+    return Err(ContractError::NotActive {});
+  }
+
+  let entropy = seed::block_entropy(&env.block);
+  let settlement_submsgs =
+    lottery.draw_round(deps.storage, &env, &config, &mut round, Some(&entropy))?;
+
+  ROUNDS.save(deps.storage, round_index, &round)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "draw"),
+        attr("round", round_index.to_string()),
+        attr("draw_height", draw_height.to_string()),
+      ])
+      .add_submessages(settlement_submsgs),
+  )
+}
@@ -1,31 +1,42 @@
 use crate::{
   error::ContractError,
-  models::{lottery::Lottery, round::Token},
-  state::{load_player, load_round, remove_player_from_round, LOTTERY},
-  utils::{build_cw20_transfer_msg, build_native_send_msg},
+  models::{lottery::Lottery, role::Role},
+  state::{ensure_role, load_player, load_round, remove_player_from_round, LOTTERY, ROUNDS},
+  utils::build_send_submsg,
 };
 use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response};
 
-/// Lottery owner can issue a refund to a specific wallet in a specific round
-/// while the round is either active or canceled.
+/// Lottery owner or a refunder can issue a refund to a specific wallet in a
+/// specific canceled round.
 pub fn issue_refund(
   deps: DepsMut,
-  env: Env,
+  _env: Env,
   info: MessageInfo,
   round_index: u32,
   recipient: &Addr,
 ) -> Result<Response, ContractError> {
   let lottery: Lottery = LOTTERY.load(deps.storage)?;
-  let mut round = load_round(deps.storage, &lottery, Some(round_index))?;
 
-  // only allow admin to issue a refund
-  if info.sender != lottery.owner {
-    return Err(ContractError::NotAuthorized {});
-  }
+  // only allow the owner or a wallet with the refunder role to issue a refund
+  ensure_role(deps.storage, &lottery, &info.sender, Role::Refunder)?;
+
+  refund_player(deps, &lottery, round_index, recipient, "issue_refund")
+}
+
+/// Remove the recipient from a canceled round and transfer them the amount
+/// they spent on tickets in it.
+pub(super) fn refund_player(
+  deps: DepsMut,
+  lottery: &Lottery,
+  round_index: u32,
+  recipient: &Addr,
+  action: &str,
+) -> Result<Response, ContractError> {
+  let mut round = load_round(deps.storage, lottery, Some(round_index))?;
 
   // only allow refunds for canceled rounds
   if !round.is_canceled() {
-    return Err(ContractError::Forbidden {});
+    return Err(ContractError::NotCanceled {});
   }
 
   // get the refund-recipient's Player data for the specified round
@@ -33,31 +44,25 @@ pub fn issue_refund(
 
   // clear this wallet and its orders from Round state
   remove_player_from_round(deps.storage, &player, round_index, &mut round)?;
+  ROUNDS.save(deps.storage, round_index, &round)?;
 
   // compute amount owed to refund-claimer
-  let round_config = lottery.get_config();
+  let round_config = lottery.get_round_config(&round);
   let refund_amount = player.amount_spent_in_round(round_config);
 
-  // initialize response
-  let response = Response::new().add_attributes(vec![
-    attr("action", "issue_refund"),
-    attr("recipient", recipient.to_string()),
-    attr("round", round_index.to_string()),
-    attr("amount", refund_amount.to_string()),
-  ]);
-
   // add cw20 or native coin transfer message to response
-  Ok(match round_config.token.clone() {
-    Token::Native { denom } => {
-      response.add_message(build_native_send_msg(&info.sender, &denom, refund_amount)?)
-    },
-    Token::Cw20 {
-      address: cw20_token_address,
-    } => response.add_submessage(build_cw20_transfer_msg(
-      &env.contract.address,
-      &info.sender,
-      &cw20_token_address,
-      refund_amount,
-    )?),
-  })
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", action),
+        attr("recipient", recipient.to_string()),
+        attr("round", round_index.to_string()),
+        attr("amount", refund_amount.to_string()),
+      ])
+      .add_submessage(build_send_submsg(
+        &round_config.token,
+        recipient,
+        refund_amount,
+      )?),
+  )
 }
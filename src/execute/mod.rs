@@ -1,11 +1,39 @@
+mod activate;
 mod add_incentives;
+mod append_rounds;
 mod buy_tickets;
+mod cancel_lottery;
+mod cancel_round;
+mod claim_refund;
 mod claim_rewards;
+mod commit_secret;
+mod draw;
 mod issue_refund;
+mod ownership;
+mod pause;
+mod receive_randomness;
+mod reveal_secret;
+mod roles;
+mod submit_beacon;
 mod terminate_round;
+mod update_config;
 
+pub use activate::activate;
 pub use add_incentives::add_incentives;
+pub use append_rounds::append_rounds;
 pub use buy_tickets::buy_tickets;
+pub use cancel_lottery::cancel_lottery;
+pub use cancel_round::cancel_round;
+pub use claim_refund::claim_refund;
 pub use claim_rewards::claim_rewards;
+pub use commit_secret::commit_secret;
+pub use draw::draw;
 pub use issue_refund::issue_refund;
+pub use ownership::{accept_owner, propose_owner, renounce_ownership};
+pub use pause::{pause, resume};
+pub use receive_randomness::receive_randomness;
+pub use reveal_secret::reveal_secret;
+pub use roles::{grant_role, revoke_role};
+pub use submit_beacon::submit_beacon;
 pub use terminate_round::terminate_round;
+pub use update_config::update_config;
@@ -0,0 +1,66 @@
+use crate::{error::ContractError, models::lottery::Lottery, state::LOTTERY};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response};
+
+/// Lottery owner can propose a new owner, who must accept ownership before
+/// it's transferred.
+pub fn propose_owner(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  new_owner: &Addr,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  if !lottery.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  lottery.pending_owner = Some(deps.api.addr_validate(new_owner.as_str())?);
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "propose_owner"),
+    attr("pending_owner", new_owner.to_string()),
+  ]))
+}
+
+/// The proposed owner accepts ownership of the lottery.
+pub fn accept_owner(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  if lottery.pending_owner.as_ref() != Some(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  lottery.owner = lottery.pending_owner.take();
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "accept_owner"),
+    attr("owner", info.sender.to_string()),
+  ]))
+}
+
+/// Lottery owner can give up ownership for good, after which only wallets
+/// with roles can perform privileged actions.
+pub fn renounce_ownership(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  if !lottery.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  lottery.owner = None;
+  lottery.pending_owner = None;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(Response::new().add_attributes(vec![attr("action", "renounce_ownership")]))
+}
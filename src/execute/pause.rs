@@ -0,0 +1,72 @@
+use crate::{
+  error::ContractError,
+  models::{
+    lottery::{Lottery, LotteryStatus},
+    role::Role,
+    round::RoundStatus,
+  },
+  state::{ensure_role, load_round, LOTTERY, ROUNDS},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Lottery owner or a pauser can pause an active lottery, blocking new ticket
+/// orders and incentives while leaving claims and refunds open.
+pub fn pause(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+  set_status(
+    deps,
+    &mut lottery,
+    info,
+    LotteryStatus::Active,
+    LotteryStatus::Paused,
+    "pause",
+  )
+}
+
+/// Lottery owner or a pauser can resume a paused lottery, starting its current
+/// round if the round was created while the lottery was paused.
+pub fn resume(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+  let mut round = load_round(deps.storage, &lottery, None)?;
+  if round.status == RoundStatus::Pending {
+    round.start(env.block.time);
+    ROUNDS.save(deps.storage, round.index, &round)?;
+  }
+  set_status(
+    deps,
+    &mut lottery,
+    info,
+    LotteryStatus::Paused,
+    LotteryStatus::Active,
+    "resume",
+  )
+}
+
+fn set_status(
+  deps: DepsMut,
+  lottery: &mut Lottery,
+  info: MessageInfo,
+  from_status: LotteryStatus,
+  to_status: LotteryStatus,
+  action: &str,
+) -> Result<Response, ContractError> {
+  // only allow the owner or a wallet with the pauser role to pause or resume the lottery
+  ensure_role(deps.storage, lottery, &info.sender, Role::Pauser)?;
+
+  if lottery.status != from_status {
+    return Err(ContractError::Forbidden {});
+  }
+
+  lottery.status = to_status;
+  LOTTERY.save(deps.storage, lottery)?;
+
+  Ok(Response::new().add_attributes(vec![attr("action", action)]))
+}
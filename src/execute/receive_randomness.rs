@@ -0,0 +1,51 @@
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, round::RandomnessSource},
+  random::proxy,
+  state::{load_round, LOTTERY, ROUNDS},
+};
+use cosmwasm_std::{attr, Binary, DepsMut, Env, MessageInfo, Response};
+
+/// Callback executed by the randomness proxy contract with the randomness
+/// requested when a round closed, mixing it into the round's seed and
+/// drawing its winners.
+pub fn receive_randomness(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  job_id: &str,
+  randomness: &Binary,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+  let round_index = proxy::parse_job_id(job_id).ok_or(ContractError::RoundNotFound {})?;
+  let mut round = load_round(deps.storage, &lottery, Some(round_index))?;
+  let config = lottery.get_round_config(&round).clone();
+
+  // only accept randomness from the round's proxy contract
+  match &config.randomness {
+    Some(RandomnessSource::Proxy { address, .. }) if *address == info.sender => {},
+    _ => return Err(ContractError::NotAuthorized {}),
+  }
+
+  // abort unless the round is waiting on the randomness to be drawn, which
+  // is too late once its players can be refunded
+  if !round.is_closed() || round.is_draw_expired(&config, &env.block) {
+    return Err(ContractError::NotActive {});
+  }
+
+  let settlement_submsgs =
+    lottery.draw_round(deps.storage, &env, &config, &mut round, Some(randomness))?;
+
+  ROUNDS.save(deps.storage, round_index, &round)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "receive_randomness"),
+        attr("round", round_index.to_string()),
+        attr("job_id", job_id),
+      ])
+      .add_submessages(settlement_submsgs),
+  )
+}
@@ -0,0 +1,54 @@
+use crate::{
+  error::ContractError,
+  models::lottery::Lottery,
+  random::seed,
+  state::{load_round, COMMITMENTS, LOTTERY, ROUNDS},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Reveal the secret committed to for a closed round, mixing it into the
+/// round's seed and drawing its winners.
+pub fn reveal_secret(
+  deps: DepsMut,
+  env: Env,
+  _info: MessageInfo,
+  round_index: u32,
+  secret: &str,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+  let mut round = load_round(deps.storage, &lottery, Some(round_index))?;
+  let config = lottery.get_round_config(&round).clone();
+
+  // abort unless the round is waiting on the secret to be drawn
+  if !round.is_closed() || round.is_draw_expired(&config, &env.block) {
+    return Err(ContractError::NotActive {});
+  }
+
+  // abort if the secret doesn't match the commitment
+  let commitment = COMMITMENTS
+    .may_load(deps.storage, round_index)?
+    .ok_or(ContractError::MissingCommitment {})?;
+  if seed::commit(secret) != commitment {
+    return Err(ContractError::InvalidSecret {});
+  }
+
+  let settlement_submsgs = lottery.draw_round(
+    deps.storage,
+    &env,
+    &config,
+    &mut round,
+    Some(secret.as_bytes()),
+  )?;
+
+  ROUNDS.save(deps.storage, round_index, &round)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "reveal_secret"),
+        attr("round", round_index.to_string()),
+      ])
+      .add_submessages(settlement_submsgs),
+  )
+}
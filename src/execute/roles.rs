@@ -0,0 +1,67 @@
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, role::Role},
+  state::{LOTTERY, ROLES},
+};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response};
+
+/// Lottery owner can grant a role to a wallet.
+pub fn grant_role(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  wallet: &Addr,
+  role: Role,
+) -> Result<Response, ContractError> {
+  let lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  if !lottery.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  let wallet = deps.api.addr_validate(wallet.as_str())?;
+  let mut roles = ROLES
+    .may_load(deps.storage, wallet.clone())?
+    .unwrap_or_default();
+  if !roles.contains(&role) {
+    roles.push(role.clone());
+  }
+  ROLES.save(deps.storage, wallet.clone(), &roles)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "grant_role"),
+    attr("wallet", wallet.to_string()),
+    attr("role", format!("{:?}", role)),
+  ]))
+}
+
+/// Lottery owner can revoke a role from a wallet.
+pub fn revoke_role(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  wallet: &Addr,
+  role: Role,
+) -> Result<Response, ContractError> {
+  let lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  if !lottery.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  let mut roles = ROLES
+    .may_load(deps.storage, wallet.clone())?
+    .unwrap_or_default();
+  roles.retain(|x| *x != role);
+  if roles.is_empty() {
+    ROLES.remove(deps.storage, wallet.clone());
+  } else {
+    ROLES.save(deps.storage, wallet.clone(), &roles)?;
+  }
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "revoke_role"),
+    attr("wallet", wallet.to_string()),
+    attr("role", format!("{:?}", role)),
+  ]))
+}
@@ -0,0 +1,45 @@
+use crate::{
+  error::ContractError,
+  models::lottery::Lottery,
+  random::drand,
+  state::{load_round, LOTTERY, ROUNDS},
+};
+use cosmwasm_std::{attr, Binary, DepsMut, Env, MessageInfo, Response};
+
+/// Anyone can submit the drand beacon targeted by a closed round, mixing its
+/// verified randomness into the round's seed and drawing its winners.
+pub fn submit_beacon(
+  deps: DepsMut,
+  env: Env,
+  _info: MessageInfo,
+  round_index: u32,
+  signature: &Binary,
+  previous_signature: &Binary,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+  let mut round = load_round(deps.storage, &lottery, Some(round_index))?;
+  let config = lottery.get_round_config(&round).clone();
+
+  // abort unless the round is waiting on a drand beacon to be drawn
+  let drand_round = match round.drand_round {
+    Some(drand_round) if round.is_closed() => drand_round,
+    _ => return Err(ContractError::NotActive {}),
+  };
+
+  let randomness = drand::verify(drand_round, previous_signature, signature)?;
+  let settlement_submsgs =
+    lottery.draw_round(deps.storage, &env, &config, &mut round, Some(&randomness))?;
+
+  ROUNDS.save(deps.storage, round_index, &round)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "submit_beacon"),
+        attr("round", round_index.to_string()),
+        attr("drand_round", drand_round.to_string()),
+      ])
+      .add_submessages(settlement_submsgs),
+  )
+}
@@ -1,15 +1,12 @@
 use crate::{
   error::ContractError,
-  models::{
-    lottery::Lottery,
-    round::{RoyaltyRecipient, Token},
-  },
-  state::{load_round, LOTTERY},
-  utils::{build_cw20_transfer_msg, build_native_send_msg},
+  models::lottery::Lottery,
+  state::{load_round, LOTTERY, ROUNDS, WINNERS},
 };
-use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Order, Response};
 
-/// Winners and Royalty Recipients claim rewards with this method.
+/// Anyone can end the current round with this method once the round's end
+/// condition is met, like after its duration has elapsed.
 pub fn terminate_round(
   deps: DepsMut,
   env: Env,
@@ -17,21 +14,43 @@ pub fn terminate_round(
 ) -> Result<Response, ContractError> {
   let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
   let mut round = load_round(deps.storage, &lottery, None)?;
-  let config = lottery.get_config().clone();
+  let config = lottery.get_round_config(&round).clone();
 
-  let royalties: Vec<RoyaltyRecipient> = if round.should_end(&config, env.block.time) {
-    if lottery.rounds.index < lottery.rounds.count {
-      lottery.end_round(deps.storage, &env, &info, &config, &mut round)?
-    } else {
-      vec![]
-    }
+  let settlement_submsgs = if round.is_closed() {
+    // refund players if the randomness needed to draw the round never arrived
+    lottery.expire_round(deps.storage, &env, &info, &config, &mut round)?
   } else {
-    vec![]
+    // abort if the round isn't ready to end yet
+    if !round.should_end(&config, &env.block) {
+      return Err(ContractError::RoundNotReady {});
+    }
+    lottery.end_round(deps.storage, &env, &info, &config, &mut round)?
   };
 
-  let response = Response::new().add_attributes(vec![attr("action", "terminate_round")]);
+  ROUNDS.save(deps.storage, round.index, &round)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  let pot_size = round.get_pot_size(&config);
+  let winners: Vec<String> = WINNERS
+    .prefix(round.index)
+    .range(deps.storage, None, None, Order::Ascending)
+    .filter_map(|entry| entry.ok())
+    .map(|(_, winner)| winner.wallet.to_string())
+    .collect();
 
-  // TODO: create CW messages for royalty transfers
+  let mut attrs = vec![
+    attr("action", "terminate_round"),
+    attr("round", round.index.to_string()),
+    attr("pot_size", pot_size.to_string()),
+  ];
+  // attribute values can't be empty, so rounds without winners omit them
+  if !winners.is_empty() {
+    attrs.push(attr("winners", winners.join(",")));
+  }
 
-  Ok(response)
+  Ok(
+    Response::new()
+      .add_attributes(attrs)
+      .add_submessages(settlement_submsgs),
+  )
 }
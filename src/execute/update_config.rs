@@ -0,0 +1,30 @@
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, role::Role, round::Config},
+  state::{ensure_role, LOTTERY},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Lottery owner or a config editor can replace a round config for rounds that
+/// haven't started yet. Existing rounds keep the config they were created with,
+/// unless the current round is pending and has no orders.
+pub fn update_config(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  config_index: u32,
+  config: &Config,
+) -> Result<Response, ContractError> {
+  let mut lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  // only allow the owner or a wallet with the config editor role to update configs
+  ensure_role(deps.storage, &lottery, &info.sender, Role::ConfigEditor)?;
+
+  lottery.update_config(deps.storage, config_index as usize, config)?;
+  LOTTERY.save(deps.storage, &lottery)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "update_config"),
+    attr("index", config_index.to_string()),
+  ]))
+}
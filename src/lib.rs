@@ -1,9 +1,13 @@
 pub mod contract;
 mod error;
 pub mod execute;
+mod migrations;
 pub mod models;
 pub mod msg;
 pub mod query;
 mod random;
+mod selection;
 pub mod state;
+#[cfg(test)]
+mod testing;
 mod utils;
@@ -0,0 +1,131 @@
+use crate::error::ContractError;
+use cosmwasm_std::{DepsMut, Env};
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
+
+mod v0_15_0;
+
+/// cw2 contract name set by versions prior to 0.15.0.
+const LEGACY_CONTRACT_NAME: &str = "crates.io:cw-contract-template";
+
+type Transform = fn(&mut DepsMut, &Env) -> Result<(), ContractError>;
+
+/// Ordered state transforms, each applied when migrating from a version
+/// older than the version it's listed with.
+const MIGRATIONS: &[(&str, Transform)] = &[("0.15.0", v0_15_0::migrate)];
+
+/// Upgrade state written by any previous version of the contract to the
+/// layout expected by the current version.
+pub fn migrate(
+  mut deps: DepsMut,
+  env: &Env,
+  contract_name: &str,
+  contract_version: &str,
+) -> Result<(), ContractError> {
+  let stored = get_contract_version(deps.storage)?;
+
+  // abort if the stored contract isn't a previous version of this contract
+  if stored.contract != contract_name && stored.contract != LEGACY_CONTRACT_NAME {
+    return Err(ContractError::MigrationError {
+      reason: format!("cannot migrate from {}", stored.contract),
+    });
+  }
+
+  let stored_version = parse_version(&stored.version)?;
+  let new_version = parse_version(contract_version)?;
+
+  // abort if migrating to an older version
+  if stored_version > new_version {
+    return Err(ContractError::MigrationError {
+      reason: format!(
+        "cannot downgrade from {} to {}",
+        stored_version, new_version
+      ),
+    });
+  }
+
+  for (version, transform) in MIGRATIONS.iter() {
+    if stored_version < parse_version(version)? {
+      transform(&mut deps, env)?;
+    }
+  }
+
+  set_contract_version(deps.storage, contract_name, contract_version)?;
+  Ok(())
+}
+
+fn parse_version(version: &str) -> Result<Version, ContractError> {
+  Version::parse(version).map_err(|_| ContractError::MigrationError {
+    reason: format!("invalid version {}", version),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{contract, msg::MigrateMsg, testing::mock_instantiate};
+  use cosmwasm_std::testing::{mock_dependencies, mock_env};
+  use cw2::{get_contract_version, set_contract_version};
+
+  use super::*;
+
+  /// Run the migration, returning the reason it failed for.
+  fn migration_error(deps: DepsMut) -> String {
+    match contract::migrate(deps, mock_env(), MigrateMsg {}) {
+      Err(ContractError::MigrationError { reason }) => reason,
+      result => panic!("unexpected result: {:?}", result),
+    }
+  }
+
+  #[test]
+  fn migrates_from_the_legacy_contract_name() {
+    let mut deps = mock_dependencies();
+    mock_instantiate(deps.as_mut(), 2);
+    let current = get_contract_version(&deps.storage).unwrap();
+    set_contract_version(&mut deps.storage, LEGACY_CONTRACT_NAME, "0.14.0").unwrap();
+
+    contract::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(get_contract_version(&deps.storage).unwrap(), current);
+
+    // migrating again to the same version is a no-op
+    contract::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(get_contract_version(&deps.storage).unwrap(), current);
+  }
+
+  #[test]
+  fn rejects_downgrades() {
+    let mut deps = mock_dependencies();
+    mock_instantiate(deps.as_mut(), 2);
+    let current = get_contract_version(&deps.storage).unwrap();
+    set_contract_version(&mut deps.storage, &current.contract, "99.0.0").unwrap();
+
+    assert_eq!(
+      migration_error(deps.as_mut()),
+      format!("cannot downgrade from 99.0.0 to {}", current.version)
+    );
+    assert_eq!(
+      get_contract_version(&deps.storage).unwrap().version,
+      "99.0.0"
+    );
+  }
+
+  #[test]
+  fn rejects_other_contracts() {
+    let mut deps = mock_dependencies();
+    mock_instantiate(deps.as_mut(), 2);
+    set_contract_version(&mut deps.storage, "crates.io:cw20-base", "0.14.0").unwrap();
+
+    assert_eq!(
+      migration_error(deps.as_mut()),
+      "cannot migrate from crates.io:cw20-base"
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_versions() {
+    let mut deps = mock_dependencies();
+    mock_instantiate(deps.as_mut(), 2);
+    set_contract_version(&mut deps.storage, LEGACY_CONTRACT_NAME, "latest").unwrap();
+
+    assert_eq!(migration_error(deps.as_mut()), "invalid version latest");
+  }
+}
@@ -0,0 +1,305 @@
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, round::Round, royalties::Claim, ticket_order::TicketOrder},
+  state::{CLAIMS, LOTTERY, ORDERS, ROUNDS, TICKETS},
+};
+use cosmwasm_std::{Addr, DepsMut, Env, Order, Uint128};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+/// Claim layout prior to 0.15.0, holding a single amount of the ticket token.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct LegacyClaim {
+  wallet: Addr,
+  amount: Uint128,
+}
+
+const LEGACY_CLAIMS: Map<Addr, LegacyClaim> = Map::new("claims");
+
+/// TicketOrder layout prior to 0.15.0, without ticket numbers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct LegacyTicketOrder {
+  wallet: Addr,
+  ticket_count: u32,
+  message: Option<String>,
+  is_public: bool,
+}
+
+const LEGACY_ORDERS: Map<(u32, u32), LegacyTicketOrder> = Map::new("orders");
+
+/// Convert single-amount claims into multi-token claims, denominated in the
+/// ticket token of the lottery's current round config, number the tickets of
+/// existing orders in the order they were placed, and give each existing round
+/// a copy of the config it was created with.
+pub fn migrate(
+  deps: &mut DepsMut,
+  _env: &Env,
+) -> Result<(), ContractError> {
+  let lottery: Lottery = LOTTERY.load(deps.storage)?;
+  let token = lottery.resolve_config(lottery.rounds.index).token.clone();
+
+  let legacy_claims: Vec<LegacyClaim> = LEGACY_CLAIMS
+    .range(deps.storage, None, None, Order::Ascending)
+    .map(|entry| entry.map(|(_, claim)| claim))
+    .collect::<Result<Vec<LegacyClaim>, _>>()?;
+
+  for legacy_claim in legacy_claims.iter() {
+    let mut claim = Claim::new(&legacy_claim.wallet);
+    claim.increment(&token, legacy_claim.amount);
+    CLAIMS.save(deps.storage, legacy_claim.wallet.clone(), &claim)?;
+  }
+
+  let legacy_orders: Vec<((u32, u32), LegacyTicketOrder)> = LEGACY_ORDERS
+    .range(deps.storage, None, None, Order::Ascending)
+    .collect::<Result<Vec<_>, _>>()?;
+
+  let mut prev_round_index: Option<u32> = None;
+  let mut next_ticket = 1u32;
+
+  for ((round_index, order_index), legacy_order) in legacy_orders.into_iter() {
+    if prev_round_index != Some(round_index) {
+      prev_round_index = Some(round_index);
+      next_ticket = 1;
+    }
+    let order = TicketOrder {
+      wallet: legacy_order.wallet,
+      first_ticket: next_ticket,
+      ticket_count: legacy_order.ticket_count,
+      message: legacy_order.message,
+      is_public: legacy_order.is_public,
+    };
+    next_ticket = order.next_ticket();
+    ORDERS.save(deps.storage, (round_index, order_index), &order)?;
+    TICKETS.save(
+      deps.storage,
+      (round_index, order.first_ticket),
+      &order_index,
+    )?;
+  }
+
+  let rounds: Vec<(u32, Round)> = ROUNDS
+    .range(deps.storage, None, None, Order::Ascending)
+    .collect::<Result<Vec<_>, _>>()?;
+
+  for (round_index, mut round) in rounds.into_iter() {
+    if round.config.is_none() {
+      round.config = Some(lottery.resolve_config(round_index).clone());
+      round.config_index = Some(lottery.get_round_config_index(&round) as u32);
+      ROUNDS.save(deps.storage, round_index, &round)?;
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    contract,
+    models::{
+      incentive::TokenAmount,
+      lottery::LotteryStatus,
+      round::{RoundStatus, Token, WinnerSelectionMethod},
+    },
+    msg::{ExecuteMsg, MigrateMsg},
+    state::SEED,
+  };
+  use cosmwasm_std::{
+    coins,
+    testing::{mock_dependencies, mock_env, mock_info},
+    Storage, Timestamp,
+  };
+  use cw2::set_contract_version;
+
+  // a round config, lottery and rounds as stored by version 0.14.0
+  const LEGACY_CONFIG: &str = r#"{
+    "name": null,
+    "targets": { "funding_level": null, "duration_minutes": null },
+    "selection": {
+      "method": { "percent": { "pct": 50, "max": null } },
+      "with_replacement": false
+    },
+    "token": { "native": { "denom": "ujuno" } },
+    "ticket_price": "100",
+    "max_tickets_per_wallet": null,
+    "royalties": []
+  }"#;
+
+  fn legacy_lottery() -> String {
+    format!(
+      r#"{{
+        "owner": "creator",
+        "name": "legacy",
+        "tournament": null,
+        "status": "active",
+        "rounds": {{ "configs": [{}], "index": 1, "count": 3 }}
+      }}"#,
+      LEGACY_CONFIG
+    )
+  }
+
+  const LEGACY_ROUNDS: [&str; 2] = [
+    r#"{
+      "status": "complete",
+      "counts": { "drawings": 1, "wallets": 2, "tickets": 8, "orders": 3 },
+      "started_at": "1000000000000",
+      "ended_by": "alice",
+      "index": 0
+    }"#,
+    r#"{
+      "status": "active",
+      "counts": { "drawings": 0, "wallets": 1, "tickets": 3, "orders": 1 },
+      "started_at": "2000000000000",
+      "ended_by": null,
+      "index": 1
+    }"#,
+  ];
+
+  fn legacy_order(
+    wallet: &str,
+    ticket_count: u32,
+  ) -> LegacyTicketOrder {
+    LegacyTicketOrder {
+      wallet: Addr::unchecked(wallet),
+      ticket_count,
+      message: None,
+      is_public: false,
+    }
+  }
+
+  /// Write the state of a 0.14.0 lottery in its 2nd round, in which alice and
+  /// bob played the 1st round and bob is playing the 2nd.
+  fn setup_legacy_state(storage: &mut dyn Storage) {
+    set_contract_version(storage, "crates.io:cw-contract-template", "0.14.0").unwrap();
+    storage.set(b"lottery", legacy_lottery().as_bytes());
+    for (round_index, round) in LEGACY_ROUNDS.iter().enumerate() {
+      storage.set(&ROUNDS.key(round_index as u32), round.as_bytes());
+    }
+    SEED.save(storage, &"seed".to_owned()).unwrap();
+
+    let alice = Addr::unchecked("alice");
+    LEGACY_CLAIMS
+      .save(
+        storage,
+        alice.clone(),
+        &LegacyClaim {
+          wallet: alice,
+          amount: Uint128::from(50u128),
+        },
+      )
+      .unwrap();
+    for (key, order) in [
+      ((0, 0), legacy_order("alice", 2)),
+      ((0, 1), legacy_order("bob", 1)),
+      ((0, 2), legacy_order("alice", 5)),
+      ((1, 0), legacy_order("bob", 3)),
+    ] {
+      LEGACY_ORDERS.save(storage, key, &order).unwrap();
+    }
+  }
+
+  #[test]
+  fn migrates_legacy_state() {
+    let mut deps = mock_dependencies();
+    setup_legacy_state(&mut deps.storage);
+
+    contract::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    // the lottery loads, with its new fields left unset
+    let lottery = LOTTERY.load(&deps.storage).unwrap();
+    assert_eq!(lottery.owner, Some(Addr::unchecked("creator")));
+    assert_eq!(lottery.pending_owner, None);
+    assert_eq!(lottery.start_at, None);
+    assert_eq!(lottery.status, LotteryStatus::Active);
+    let config = &lottery.rounds.configs[0];
+    assert_eq!(
+      config.selection.method,
+      WinnerSelectionMethod::Percent { pct: 50, max: None }
+    );
+    assert_eq!(config.targets.wallet_count, None);
+    assert_eq!(config.min_wallets, None);
+    assert_eq!(config.randomness, None);
+    assert_eq!(config.jackpot, None);
+
+    // and each round loads with a copy of its config
+    for round_index in 0..2 {
+      let round = ROUNDS.load(&deps.storage, round_index).unwrap();
+      assert_eq!(round.config.as_ref(), Some(config));
+      assert_eq!(round.config_index, Some(0));
+      assert!(round.carryover.is_zero());
+      assert_eq!(round.ends_at, None);
+      assert_eq!(round.jackpot, None);
+    }
+    let round = ROUNDS.load(&deps.storage, 1).unwrap();
+    assert_eq!(round.status, RoundStatus::Active);
+    assert_eq!(round.started_at, Some(Timestamp::from_seconds(2000)));
+
+    // claims are denominated in the lottery's ticket token
+    let claim = CLAIMS
+      .load(&deps.storage, Addr::unchecked("alice"))
+      .unwrap();
+    assert_eq!(
+      claim.balances,
+      vec![TokenAmount {
+        token: Token::Native {
+          denom: "ujuno".to_owned(),
+        },
+        amount: Uint128::from(50u128),
+      }]
+    );
+    assert!(claim.rounds.is_empty());
+
+    // tickets are numbered from 1 in each round, in the order they were bought
+    let orders: Vec<((u32, u32), TicketOrder)> = ORDERS
+      .range(&deps.storage, None, None, Order::Ascending)
+      .collect::<Result<_, _>>()
+      .unwrap();
+    let numbering: Vec<((u32, u32), &str, u32, u32)> = orders
+      .iter()
+      .map(|(key, order)| {
+        (
+          *key,
+          order.wallet.as_str(),
+          order.first_ticket,
+          order.ticket_count,
+        )
+      })
+      .collect();
+    assert_eq!(
+      numbering,
+      vec![
+        ((0, 0), "alice", 1, 2),
+        ((0, 1), "bob", 3, 1),
+        ((0, 2), "alice", 4, 5),
+        ((1, 0), "bob", 1, 3),
+      ]
+    );
+
+    // and indexed by their first ticket for drawing
+    let tickets: Vec<((u32, u32), u32)> = TICKETS
+      .range(&deps.storage, None, None, Order::Ascending)
+      .collect::<Result<_, _>>()
+      .unwrap();
+    assert_eq!(
+      tickets,
+      vec![((0, 1), 0), ((0, 3), 1), ((0, 4), 2), ((1, 1), 0)]
+    );
+
+    // tickets bought in the current round are numbered after the migrated ones
+    contract::execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("carol", &coins(200, "ujuno")),
+      ExecuteMsg::BuyTickets {
+        count: 2,
+        message: None,
+        is_public: None,
+      },
+    )
+    .unwrap();
+    let order = ORDERS.load(&deps.storage, (1, 1)).unwrap();
+    assert_eq!(order.first_ticket, 4);
+    assert_eq!(ROUNDS.load(&deps.storage, 1).unwrap().counts.tickets, 5);
+  }
+}
@@ -11,12 +11,30 @@ pub struct TokenAmount {
   pub amount: Uint128,
 }
 
+/// Increment the balance of the given token in a list of token balances,
+/// adding an entry for the token if there isn't one yet.
+pub fn increment_balance(
+  balances: &mut Vec<TokenAmount>,
+  token: &Token,
+  amount: Uint128,
+) {
+  if let Some(balance) = balances.iter_mut().find(|x| x.token == *token) {
+    balance.amount += amount;
+  } else {
+    balances.push(TokenAmount {
+      token: token.clone(),
+      amount,
+    });
+  }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Reward {
   // the token received as a reward
   pub token: Option<TokenAmount>,
 
-  // the position who receives the reward (1st place, 2nd, etc.)
+  // the position who receives the reward (1st place, 2nd, etc.). if none,
+  // the reward is split evenly among all winners.
   pub position: Option<u32>,
 }
 
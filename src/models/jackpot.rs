@@ -0,0 +1,44 @@
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::{
+  incentive::{increment_balance, TokenAmount},
+  round::Token,
+};
+
+/// Reserve of the lottery's progressive jackpot, holding the share of each
+/// drawn round's pot set aside until the jackpot is won.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Jackpot {
+  pub balances: Vec<TokenAmount>,
+  // index of the last round that contributed to the reserve
+  pub last_round: Option<u32>,
+}
+
+impl Jackpot {
+  /// Increment the reserve's balance of the given token.
+  pub fn increment(
+    &mut self,
+    token: &Token,
+    amount: Uint128,
+  ) {
+    increment_balance(&mut self.balances, token, amount);
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.balances.iter().all(|x| x.amount.is_zero())
+  }
+}
+
+/// The jackpot won in a round, along with the ticket that won it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JackpotWin {
+  pub wallet: Addr,
+  pub ticket: u32,
+  pub balances: Vec<TokenAmount>,
+  // true if the jackpot was awarded without drawing against its odds because
+  // the round was the lottery's last
+  #[serde(default)]
+  pub forced: bool,
+}
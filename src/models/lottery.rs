@@ -1,19 +1,23 @@
-use cw_storage_plus::KeyDeserialize;
-use std::{collections::HashSet, iter};
-
 use crate::{
   error::ContractError,
   msg::InstantiateMsg,
-  random::{pcg64_from_seed, seed},
-  state::{CLAIMS, PLAYERS, ROUNDS, SEED},
-  utils::apply_pct,
+  random::{drand, pcg64_from_seed, proxy, seed, TicketPool},
+  selection,
+  state::{
+    remove_player_from_round, CLAIMS, INCENTIVES, JACKPOT, PLAYERS, ROUNDS, ROUND_SEEDS, SEED,
+    WINNERS,
+  },
+  utils::{apply_pct, build_royalty_transfer_submsgs, build_send_submsg},
 };
 
 use super::{
-  round::{Config, Round, RoundStatus, RoyaltyRecipient, WinnerSelectionMethod},
+  jackpot::{Jackpot, JackpotWin},
+  player::Player,
+  round::{Config, RandomnessSource, Round, RoundStatus, RoyaltyRecipient, ThresholdPolicy, Token},
   royalties::Claim,
+  winner::{Selection, Winner},
 };
-use cosmwasm_std::{Addr, Env, MessageInfo, Order, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Env, MessageInfo, Order, StdResult, Storage, SubMsg, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +26,7 @@ use serde::{Deserialize, Serialize};
 pub enum LotteryStatus {
   Pending,
   Active,
+  Paused,
   Complete,
   Canceled,
 }
@@ -35,23 +40,30 @@ pub struct Rounds {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Lottery {
-  pub owner: Addr,
+  pub owner: Option<Addr>,
+  pub pending_owner: Option<Addr>,
   pub name: Option<String>,
   pub tournament: Option<bool>,
   pub status: LotteryStatus,
   pub rounds: Rounds,
+  pub start_at: Option<Timestamp>,
 }
 
 impl Lottery {
   pub fn instantiate(
+    env: &Env,
     info: &MessageInfo,
     msg: &InstantiateMsg,
   ) -> Result<Self, ContractError> {
+    // a lottery with a scheduled start remains pending until then
+    let is_scheduled = matches!(msg.start_at, Some(t) if t > env.block.time);
     let lottery = Lottery {
-      owner: info.sender.clone(),
+      owner: Some(info.sender.clone()),
+      pending_owner: None,
       name: msg.name.clone(),
       tournament: msg.tournament,
-      status: if msg.activate.unwrap_or(true) {
+      start_at: msg.start_at,
+      status: if msg.activate.unwrap_or(true) && !is_scheduled {
         LotteryStatus::Active
       } else {
         LotteryStatus::Pending
@@ -66,20 +78,124 @@ impl Lottery {
     Ok(lottery)
   }
 
-  pub fn get_config_index(&self) -> usize {
-    (self.rounds.index.clone() as usize) % self.rounds.configs.len()
+  /// Return the config that a round with the given index was created with
+  /// before configs could be appended, when rounds cycled through the configs
+  /// by their index.
+  pub fn resolve_config(
+    &self,
+    round_index: u32,
+  ) -> &Config {
+    &self.rounds.configs[self.resolve_config_index(round_index)]
+  }
+
+  fn resolve_config_index(
+    &self,
+    round_index: u32,
+  ) -> usize {
+    (round_index as usize) % self.rounds.configs.len()
+  }
+
+  /// Return the index of the config that an existing round was created with.
+  pub fn get_round_config_index(
+    &self,
+    round: &Round,
+  ) -> usize {
+    round
+      .config_index
+      .map(|config_index| config_index as usize)
+      .unwrap_or_else(|| self.resolve_config_index(round.index))
   }
 
-  pub fn get_config(&self) -> &Config {
-    &self.rounds.configs[self.get_config_index()]
+  /// Return the config of an existing round, as resolved when it was created.
+  pub fn get_round_config<'a>(
+    &'a self,
+    round: &'a Round,
+  ) -> &'a Config {
+    round
+      .config
+      .as_ref()
+      .unwrap_or_else(|| self.resolve_config(round.index))
+  }
+
+  /// Return the index of the config that the round after the given one is
+  /// created with. Rounds cycle through the configs in order, so configs
+  /// appended to the list take their turn after the existing ones.
+  fn get_next_config_index(
+    &self,
+    round: &Round,
+  ) -> usize {
+    (self.get_round_config_index(round) + 1) % self.rounds.configs.len()
   }
 
   pub fn validate(&self) -> Result<(), ContractError> {
-    if self.rounds.configs.len() == 0 {
+    if self.rounds.configs.is_empty() {
       return Err(ContractError::ValidationError {
         reason: Some("lottery must have at least 1 round config".to_owned()),
       });
     }
+    if self.rounds.count <= self.rounds.index {
+      return Err(ContractError::ValidationError {
+        reason: Some("lottery round count must exceed the current round index".to_owned()),
+      });
+    }
+    for config in self.rounds.configs.iter() {
+      config.validate()?;
+    }
+    Ok(())
+  }
+
+  /// Replace the round config at the given index in the list of configs. Each
+  /// round keeps a copy of its config, so this only affects rounds created
+  /// afterwards and a current round created with the config that hasn't
+  /// started or taken any orders yet.
+  pub fn update_config(
+    &mut self,
+    storage: &mut dyn Storage,
+    config_index: usize,
+    config: &Config,
+  ) -> Result<(), ContractError> {
+    if config_index >= self.rounds.configs.len() {
+      return Err(ContractError::ValidationError {
+        reason: Some("round config index out of bounds".to_owned()),
+      });
+    }
+    self.rounds.configs[config_index] = config.clone();
+    self.validate()?;
+
+    let mut round = ROUNDS.load(storage, self.rounds.index)?;
+    if round.status == RoundStatus::Pending
+      && round.counts.orders == 0
+      && self.get_round_config_index(&round) == config_index
+    {
+      round.config = Some(config.clone());
+      ROUNDS.save(storage, round.index, &round)?;
+    }
+    Ok(())
+  }
+
+  /// Append round configs and/or raise the total number of rounds. Existing
+  /// rounds keep their configs, and the appended configs take their turn
+  /// after the existing ones. If the lottery was complete, its next round
+  /// begins.
+  pub fn append_rounds(
+    &mut self,
+    storage: &mut dyn Storage,
+    env: &Env,
+    configs: &[Config],
+    count: Option<u32>,
+  ) -> Result<(), ContractError> {
+    let prev_count = self.rounds.count;
+    self.rounds.configs.extend_from_slice(configs);
+    self.rounds.count = count
+      .unwrap_or(prev_count)
+      .max(prev_count)
+      .max(self.rounds.configs.len() as u32);
+    self.validate()?;
+    if self.status == LotteryStatus::Complete && self.rounds.count > prev_count {
+      self.status = LotteryStatus::Active;
+      let round = ROUNDS.load(storage, self.rounds.index)?;
+      self.start_next_round(storage, env, &round)?;
+    }
     Ok(())
   }
 
@@ -87,6 +203,53 @@ impl Lottery {
     self.status == LotteryStatus::Active
   }
 
+  pub fn is_owner(
+    &self,
+    addr: &Addr,
+  ) -> bool {
+    self.owner.as_ref() == Some(addr)
+  }
+
+  pub fn is_ended(&self) -> bool {
+    self.status == LotteryStatus::Complete || self.status == LotteryStatus::Canceled
+  }
+
+  /// Make a pending lottery active, starting its current round.
+  pub fn activate(
+    &mut self,
+    env: &Env,
+    round: &mut Round,
+  ) -> Result<(), ContractError> {
+    if self.status != LotteryStatus::Pending {
+      return Err(ContractError::Forbidden {});
+    }
+    self.status = LotteryStatus::Active;
+    round.start(env.block.time);
+    Ok(())
+  }
+
+  /// Activate the lottery if it's pending and its scheduled start time has
+  /// passed, returning true if it was activated.
+  pub fn activate_if_scheduled(
+    &mut self,
+    env: &Env,
+    round: &mut Round,
+  ) -> Result<bool, ContractError> {
+    if self.status == LotteryStatus::Pending {
+      if let Some(start_at) = self.start_at {
+        if env.block.time >= start_at {
+          self.activate(env, round)?;
+          return Ok(true);
+        }
+      }
+    }
+    Ok(false)
+  }
+
+  /// End the given round, returning the submsgs required to settle it, like
+  /// transfers to autosend royalty recipients. If the round's winners are
+  /// drawn using randomness that isn't available yet, the round is closed
+  /// instead, to be drawn once it is.
   pub fn end_round(
     &mut self,
     storage: &mut dyn Storage,
@@ -94,219 +257,874 @@ impl Lottery {
     info: &MessageInfo,
     config: &Config,
     round: &mut Round,
-  ) -> Result<Vec<RoyaltyRecipient>, ContractError> {
+  ) -> Result<Vec<SubMsg>, ContractError> {
     if !round.is_active() {
       return Err(ContractError::NotActive {});
     }
 
     if round.counts.wallets == 0 {
+      // there's nothing to draw or refund, so the round starts over
+      round.restart(env.block.time);
       return Ok(vec![]);
-    } else if round.counts.wallets == 1 {
-      Self::refund_tickets(storage, round)?;
-      Self::refund_incentives(storage, round)?;
-      return Ok(vec![]);
+    } else if !round.meets_minimums(config) {
+      // without enough participation, either keep the round open for more
+      // players, carry its pot into the next round or cancel it with refunds.
+      match config.threshold_policy {
+        Some(ThresholdPolicy::Extend) => {
+          round.restart(env.block.time);
+          return Ok(vec![]);
+        },
+        Some(ThresholdPolicy::Rollover) if self.can_carry_over(config, round) => {
+          return self.roll_over_round(storage, env, info, config, round);
+        },
+        _ => {
+          Self::refund_tickets(storage, config, round)?;
+          return self.cancel_round(storage, env, info, round, true);
+        },
+      }
     }
 
     // end the current round
     round.ended_by = Some(info.sender.clone());
-    round.status = RoundStatus::Complete;
 
-    // increment the round index and create a the next active Round.
-    // otherwise, mark the lottery completed as a whole
-    let is_final_round = self.rounds.index == self.rounds.count - 1;
-    if is_final_round {
-      self.status = LotteryStatus::Complete;
-    } else {
-      let next_round_index = self.rounds.index + 1;
-      // create the next round
-      ROUNDS.save(
-        storage,
-        self.rounds.index + 1,
-        &Round::new(env.block.time, true, next_round_index),
-      )?;
+    // get and save new PRNG seed
+    let new_seed = seed::finalize(&SEED.load(storage)?, &info.sender, env.block.height);
+    SEED.save(storage, &new_seed)?;
+
+    // close the round if its winners are drawn using external randomness
+    if let Some(source) = &config.randomness {
+      round.status = RoundStatus::Closed;
+      round.closed_at = Some(env.block.time);
+      return match source {
+        RandomnessSource::CommitReveal { .. } => Ok(vec![]),
+        RandomnessSource::Drand {} => {
+          round.drand_round = Some(drand::next_round(env.block.time));
+          Ok(vec![])
+        },
+        RandomnessSource::Proxy { address, .. } => {
+          Ok(vec![proxy::build_request_submsg(address, round.index)?])
+        },
+        RandomnessSource::FutureBlock { delay, .. } => {
+          round.draw_height = Some(env.block.height + (*delay).max(1));
+          Ok(vec![])
+        },
+      };
     }
 
+    self.draw_round(storage, env, config, round, None)
+  }
+
+  /// Select the winners of a round that has ended, mixing the given entropy
+  /// into its seed, and start the next round. Returns the submsgs required to
+  /// settle the round.
+  pub fn draw_round(
+    &mut self,
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    round: &mut Round,
+    entropy: Option<&[u8]>,
+  ) -> Result<Vec<SubMsg>, ContractError> {
+    round.status = RoundStatus::Complete;
+    let is_final_round = self.rounds.index == self.rounds.count - 1;
+    self.start_next_round(storage, env, round)?;
+
     // calculate claimable amounts
     let total_amount = round.get_pot_size(config);
     let total_royalty_amount = round.get_total_royalty_amount(config, total_amount);
-    let total_winnings_amount = total_amount - total_royalty_amount;
+    let jackpot_amount = config
+      .jackpot
+      .as_ref()
+      .map(|jackpot| apply_pct(total_amount, jackpot.pct))
+      .unwrap_or_default();
+    let total_winnings_amount = total_amount - total_royalty_amount - jackpot_amount;
 
-    // get and save new PRNG seed
-    let new_seed = seed::finalize(&SEED.load(storage)?, &info.sender, env.block.height);
-    SEED.save(storage, &new_seed)?;
+    // mix any external entropy into the PRNG seed, keeping the round's final
+    // seed on record so that its draw can be replayed.
+    let mut new_seed = SEED.load(storage)?;
+    if let Some(entropy) = entropy {
+      new_seed = seed::mix(&new_seed, entropy);
+      SEED.save(storage, &new_seed)?;
+    }
+    ROUND_SEEDS.save(storage, round.index, &new_seed)?;
 
     // increment claimable amount for each non-autosent royalty recipient
-    Self::upsert_royalty_claims(storage, config, &round)?;
+    Self::upsert_royalty_claims(storage, config, round)?;
 
     // randomly select the winners and increment their claim records
-    Self::pick_winners_and_upsert_claims(
+    let selections = Self::pick_winners_and_upsert_claims(
       storage,
       config,
-      &round,
+      round,
       total_winnings_amount,
       &new_seed,
     )?;
 
+    // credit the winners with their share of the round's incentives
+    Self::distribute_incentives(storage, round, &selections)?;
+
+    // add to the jackpot and draw for it. the final round always awards
+    // whatever remains in the reserve, since no later round could win it.
+    if config.jackpot.is_some() || is_final_round {
+      Self::settle_jackpot(
+        storage,
+        config,
+        round,
+        jackpot_amount,
+        &new_seed,
+        is_final_round,
+      )?;
+    }
+
     // collect royalty recipients using autosend for the sake of forming
     // the required CW messages
     let royalties: Vec<RoyaltyRecipient> = config
       .royalties
       .iter()
       .filter(|x| x.autosend.unwrap_or(false))
-      .map(|x| x.clone())
+      .cloned()
       .collect();
 
-    Ok(royalties)
+    build_royalty_transfer_submsgs(&royalties, &config.token, total_amount)
   }
 
-  fn refund_tickets(
+  /// Add the round's contribution to the jackpot reserve. If the jackpot is won
+  /// in this round, credit the entire reserve to the winner, or else let it
+  /// roll forward to the next round. When forced, a non-empty reserve is won
+  /// regardless of the jackpot's odds.
+  fn settle_jackpot(
+    storage: &mut dyn Storage,
+    config: &Config,
+    round: &mut Round,
+    amount: Uint128,
+    seed: &str,
+    force: bool,
+  ) -> Result<(), ContractError> {
+    let mut jackpot = JACKPOT.may_load(storage)?.unwrap_or_default();
+    if !amount.is_zero() {
+      jackpot.increment(&config.token, amount);
+      jackpot.last_round = Some(round.index);
+    }
+    let force = force && !jackpot.is_empty();
+
+    if let Some((wallet, ticket)) =
+      Self::select_jackpot_winner(storage, config, round, seed, force)?
+    {
+      for balance in jackpot.balances.iter() {
+        Self::upsert_claim(
+          storage,
+          &wallet,
+          &balance.token,
+          balance.amount,
+          Some(round.index),
+        )?;
+      }
+      round.jackpot = Some(JackpotWin {
+        wallet,
+        ticket,
+        balances: jackpot.balances,
+        forced: force,
+      });
+      jackpot = Jackpot::default();
+    }
+
+    JACKPOT.save(storage, &jackpot)?;
+    Ok(())
+  }
+
+  /// Return whatever remains in the jackpot reserve to the winners of the last
+  /// round that contributed to it, in proportion to their winnings in that
+  /// round. This is for lotteries that end without a final draw, e.g. when
+  /// canceled, so that the reserve isn't locked in the contract for good.
+  pub fn release_jackpot(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let jackpot = JACKPOT.may_load(storage)?.unwrap_or_default();
+    // only drawn rounds contribute to the reserve, so it's empty unless one was
+    let round_index = match jackpot.last_round {
+      Some(round_index) if !jackpot.is_empty() => round_index,
+      _ => return Ok(()),
+    };
+    let winners: Vec<Winner> = WINNERS
+      .prefix(round_index)
+      .range(storage, None, None, Order::Ascending)
+      .map(|entry| entry.map(|(_, winner)| winner))
+      .collect::<StdResult<Vec<Winner>>>()?;
+    let total_winnings: Uint128 = winners.iter().map(|winner| winner.amount_total).sum();
+    if total_winnings.is_zero() {
+      return Ok(());
+    }
+    for balance in jackpot.balances.iter() {
+      let mut remainder = balance.amount;
+      for winner in winners.iter() {
+        let amount = balance
+          .amount
+          .multiply_ratio(winner.amount_total, total_winnings);
+        remainder -= amount;
+        Self::upsert_claim(storage, &winner.wallet, &balance.token, amount, None)?;
+      }
+      // any remainder left over from rounding goes to the 1st place winner
+      if !remainder.is_zero() {
+        Self::upsert_claim(storage, &winners[0].wallet, &balance.token, remainder, None)?;
+      }
+    }
+    JACKPOT.save(storage, &Jackpot::default())?;
+    Ok(())
+  }
+
+  /// Cancel a closed round whose randomness never arrived before its deadline,
+  /// crediting each player with a refund.
+  pub fn expire_round(
+    &mut self,
     storage: &mut dyn Storage,
+    env: &Env,
+    info: &MessageInfo,
+    config: &Config,
+    round: &mut Round,
+  ) -> Result<Vec<SubMsg>, ContractError> {
+    if !round.is_draw_expired(config, &env.block) {
+      return Err(ContractError::RoundNotReady {});
+    }
+    Self::refund_tickets(storage, config, round)?;
+    self.cancel_round(storage, env, info, round, true)
+  }
+
+  /// Cancel the given round, making each player's ticket order refundable and
+  /// returning the submsgs required to refund its incentives.
+  pub fn cancel_round(
+    &mut self,
+    storage: &mut dyn Storage,
+    env: &Env,
+    info: &MessageInfo,
+    round: &mut Round,
+    advance: bool,
+  ) -> Result<Vec<SubMsg>, ContractError> {
+    if !(round.is_active() || round.is_closed() || round.status == RoundStatus::Pending) {
+      return Err(ContractError::NotActive {});
+    }
+    round.ended_by = Some(info.sender.clone());
+    round.status = RoundStatus::Canceled;
+
+    // a pot carried into this round isn't refundable to its players, so it
+    // moves on to the next round if possible. otherwise, it's returned to the
+    // players of the rounds it came from.
+    let config = self.get_round_config(round).clone();
+    let carry_over = advance && self.can_carry_over(&config, round);
+    if advance {
+      self.start_next_round(storage, env, round)?;
+    }
+    if carry_over {
+      if !round.carryover.is_zero() {
+        Self::increment_carryover(
+          storage,
+          round.index + 1,
+          round.carryover,
+          &round.carryover_from,
+        )?;
+      }
+    } else {
+      Self::cancel_carryover_sources(storage, round)?;
+    }
+    if advance && self.is_ended() {
+      Self::release_jackpot(storage)?;
+    }
+    Self::refund_incentives(storage, round)
+  }
+
+  /// Return true if there's a round after the current one that uses the same
+  /// ticket token, so that the current round's pot can be carried into it.
+  fn can_carry_over(
+    &self,
+    config: &Config,
     round: &Round,
+  ) -> bool {
+    let next_config = &self.rounds.configs[self.get_next_config_index(round)];
+    self.rounds.index + 1 < self.rounds.count && next_config.token == config.token
+  }
+
+  /// End the round without drawing it, carrying its pot and incentives into
+  /// the next round.
+  fn roll_over_round(
+    &mut self,
+    storage: &mut dyn Storage,
+    env: &Env,
+    info: &MessageInfo,
+    config: &Config,
+    round: &mut Round,
+  ) -> Result<Vec<SubMsg>, ContractError> {
+    round.ended_by = Some(info.sender.clone());
+    round.status = RoundStatus::Complete;
+    self.start_next_round(storage, env, round)?;
+
+    let next_round_index = round.index + 1;
+    let mut sources = round.carryover_from.clone();
+    sources.push(round.index);
+    Self::increment_carryover(
+      storage,
+      next_round_index,
+      round.get_pot_size(config),
+      &sources,
+    )?;
+
+    if let Some(mut incentives) = INCENTIVES.may_load(storage, round.index)? {
+      INCENTIVES.update(
+        storage,
+        next_round_index,
+        |next_incentives| -> Result<_, ContractError> {
+          let mut next_incentives = next_incentives.unwrap_or_default();
+          next_incentives.append(&mut incentives);
+          Ok(next_incentives)
+        },
+      )?;
+      INCENTIVES.remove(storage, round.index);
+    }
+
+    Ok(vec![])
+  }
+
+  /// Add the given amount to the carryover of a round, along with the indices
+  /// of the rolled-over rounds whose pots it came from.
+  fn increment_carryover(
+    storage: &mut dyn Storage,
+    round_index: u32,
+    amount: Uint128,
+    sources: &[u32],
   ) -> Result<(), ContractError> {
+    let mut round = ROUNDS.load(storage, round_index)?;
+    round.carryover += amount;
+    round.carryover_from.extend_from_slice(sources);
+    ROUNDS.save(storage, round_index, &round)?;
     Ok(())
   }
 
-  fn refund_incentives(
+  /// Cancel the rolled-over rounds whose pots were carried into the given
+  /// round, so that their players can claim refunds of the carryover. Players
+  /// keep their tickets in a rolled-over round, so a refund is the amount they
+  /// spent in it.
+  fn cancel_carryover_sources(
+    storage: &mut dyn Storage,
+    round: &Round,
+  ) -> Result<(), ContractError> {
+    for source_index in round.carryover_from.iter() {
+      let mut source = ROUNDS.load(storage, *source_index)?;
+      source.status = RoundStatus::Canceled;
+      ROUNDS.save(storage, *source_index, &source)?;
+    }
+    Ok(())
+  }
+
+  /// Increment the round index and create the round after the given current
+  /// one, which is only active if the lottery is. Otherwise, mark the lottery
+  /// completed as a whole.
+  fn start_next_round(
+    &mut self,
     storage: &mut dyn Storage,
+    env: &Env,
     round: &Round,
   ) -> Result<(), ContractError> {
+    let is_final_round = self.rounds.index == self.rounds.count - 1;
+    if is_final_round {
+      self.status = LotteryStatus::Complete;
+    } else {
+      let next_round_index = self.rounds.index + 1;
+      let next_config_index = self.get_next_config_index(round);
+      ROUNDS.save(
+        storage,
+        next_round_index,
+        &Round::new(
+          env.block.time,
+          self.is_active(),
+          next_round_index,
+          next_config_index as u32,
+          &self.rounds.configs[next_config_index],
+        ),
+      )?;
+      self.rounds.index = next_round_index;
+    }
+    Ok(())
+  }
+
+  /// Credit each player in the round with a Claim for the amount they spent,
+  /// removing them from the round so that they can't claim a refund twice.
+  fn refund_tickets(
+    storage: &mut dyn Storage,
+    config: &Config,
+    round: &mut Round,
+  ) -> Result<(), ContractError> {
+    let players: Vec<Player> = PLAYERS
+      .prefix(round.index)
+      .range(storage, None, None, Order::Ascending)
+      .filter_map(|entry| entry.ok())
+      .map(|(_, player)| player)
+      .collect();
+    for player in players.iter() {
+      Self::upsert_claim(
+        storage,
+        &player.wallet,
+        &config.token,
+        player.amount_spent_in_round(config),
+        None,
+      )?;
+      remove_player_from_round(storage, player, round.index, round)?;
+    }
     Ok(())
   }
 
+  /// Return the rewards of each Incentive added to the round to its source.
+  fn refund_incentives(
+    storage: &mut dyn Storage,
+    round: &Round,
+  ) -> Result<Vec<SubMsg>, ContractError> {
+    let mut submsgs: Vec<SubMsg> = vec![];
+    for incentive in INCENTIVES
+      .may_load(storage, round.index)?
+      .unwrap_or_default()
+      .iter()
+    {
+      for reward in incentive.rewards.iter() {
+        if let Some(t) = &reward.token {
+          submsgs.push(build_send_submsg(&t.token, &incentive.source, t.amount)?);
+        }
+      }
+    }
+    INCENTIVES.remove(storage, round.index);
+    Ok(submsgs)
+  }
+
   /// Increment royalty Claims for royalty recipients without autosend.
   fn upsert_royalty_claims(
     storage: &mut dyn Storage,
     config: &Config,
     round: &Round,
   ) -> Result<(), ContractError> {
-    let total = Uint128::from(round.counts.tickets) * config.ticket_price;
+    let total = round.get_pot_size(config);
     for royalty in config
       .royalties
       .iter()
       .filter(|x| !x.autosend.unwrap_or(false))
     {
       let amount_incr = Uint128::from(royalty.pct) * total / Uint128::from(100u128);
-      CLAIMS.update(
-        storage,
-        royalty.address.clone(),
-        |some_claim| -> Result<Claim, ContractError> {
-          if let Some(mut claim) = some_claim {
-            claim.amount += amount_incr;
-            Ok(claim)
-          } else {
-            Ok(Claim {
-              wallet: royalty.address.clone(),
-              amount: amount_incr,
-            })
-          }
-        },
-      )?;
+      Self::upsert_claim(storage, &royalty.address, &config.token, amount_incr, None)?;
     }
     Ok(())
   }
 
+  /// Randomly select the winners of the round, returning each winning wallet
+  /// along with its claim pct, ordered by position.
   fn pick_winners_and_upsert_claims(
     storage: &mut dyn Storage,
     config: &Config,
     round: &Round,
     balance: Uint128,
-    seed: &String,
-  ) -> Result<(), ContractError> {
-    let mut rng = pcg64_from_seed(&seed)?;
-
-    let mut sample_pool: Vec<u32> = Vec::with_capacity(round.counts.tickets as usize);
-    let mut all_wallets: Vec<Addr> = Vec::with_capacity(round.counts.wallets as usize);
+    seed: &str,
+  ) -> Result<Vec<(Addr, u8)>, ContractError> {
+    let selections = Self::select_winners(storage, config, round, seed)?;
+    for selection in selections.iter() {
+      let claim_amount = apply_pct(balance, selection.pct);
+      WINNERS.save(
+        storage,
+        (round.index, selection.position),
+        &Winner {
+          wallet: selection.wallet.clone(),
+          position: selection.position,
+          ticket: selection.ticket,
+          amount_total: claim_amount,
+          amount_claimed: Uint128::zero(),
+          order_index: selection.order_index,
+          ticket_offset: selection.ticket_offset,
+        },
+      )?;
+      Self::upsert_claim(
+        storage,
+        &selection.wallet,
+        &config.token,
+        claim_amount,
+        Some(round.index),
+      )?;
+    }
+    Ok(
+      selections
+        .into_iter()
+        .map(|selection| (selection.wallet, selection.pct))
+        .collect(),
+    )
+  }
 
-    // create the sample pool to select winner address indices from
-    for (i, result) in PLAYERS
-      .prefix(round.index)
-      .range(storage, None, None, Order::Ascending)
-      .enumerate()
-    {
-      if let Some((addr, player)) = result.ok() {
-        all_wallets.push(addr.clone());
-        sample_pool.append(
-          &mut iter::repeat(i as u32)
-            .take(player.ticket_count as usize)
-            .collect(),
-        );
+  /// Check whether the jackpot is won in the round and, if so, draw the ticket
+  /// that wins it, returning its wallet and ticket number. When forced, the
+  /// jackpot's odds are skipped and a ticket is always drawn. Like
+  /// select_winners, this doesn't modify any state.
+  pub fn select_jackpot_winner(
+    storage: &dyn Storage,
+    config: &Config,
+    round: &Round,
+    seed: &str,
+    force: bool,
+  ) -> Result<Option<(Addr, u32)>, ContractError> {
+    // the jackpot is drawn from its own stream, independent of the winners
+    let mut rng = pcg64_from_seed(&seed::mix(&seed.to_owned(), b"jackpot"))?;
+    if !force {
+      let odds = match &config.jackpot {
+        Some(jackpot) => jackpot.odds as u64,
+        None => return Ok(None),
+      };
+      if rng.gen_range(odds.max(1)) != 0 {
+        return Ok(None);
       }
     }
-    let claim_pcts = Lottery::calculate_claim_percentages(config, round)?;
-    let n_selections = claim_pcts.len() as u32;
-    let mut visited: HashSet<Addr> = HashSet::with_capacity(n_selections as usize);
-    let mut winner_index = 0u32;
-
-    while winner_index < n_selections {
-      let i = rng.next_u64() % sample_pool.len() as u64;
-      let wallet = &all_wallets[i as usize];
-      let previously_selected = visited.contains(wallet);
-
-      // take the wallet if we're in a "multiwin" game, where there's selection
-      // WITH replacement, or else take it if we haven't seen the wallet before.
-      if config.selection.with_replacement || !previously_selected {
-        let claim_amount = apply_pct(balance, claim_pcts[winner_index as usize]);
-        visited.insert(wallet.clone());
-        Self::upsert_claim(storage, wallet, claim_amount)?;
-        winner_index += 1;
+    let pool = TicketPool::load(storage, round.index)?;
+    if pool.is_empty() {
+      return Ok(None);
+    }
+    let (entry, ticket_offset) = pool.draw(&mut rng)?;
+    Ok(Some((entry.wallet, entry.first_ticket + ticket_offset)))
+  }
+
+  /// Draw the winning tickets of the round from its ticket orders, ordered by
+  /// position. This doesn't modify any state, so given the round's final seed,
+  /// it reproduces the outcome of a past draw.
+  pub fn select_winners(
+    storage: &dyn Storage,
+    config: &Config,
+    round: &Round,
+    seed: &str,
+  ) -> Result<Vec<Selection>, ContractError> {
+    let mut rng = pcg64_from_seed(seed)?;
+    let mut pool = TicketPool::load(storage, round.index)?;
+    if pool.is_empty() {
+      return Ok(vec![]);
+    }
+    let claim_pcts = selection::claim_percentages(&config.selection.method, round.counts.wallets);
+    let mut selections: Vec<Selection> = Vec::with_capacity(claim_pcts.len());
+
+    // each iteration selects exactly one winner. when drawing WITHOUT
+    // replacement, the winner's tickets are removed from the pool, so the
+    // remaining wallets' odds stay proportional to their tickets.
+    for (winner_index, pct) in claim_pcts.iter().enumerate() {
+      if pool.is_empty() {
+        break;
       }
+      let (entry, ticket_offset) = pool.draw(&mut rng)?;
+      let selection = Selection {
+        wallet: entry.wallet,
+        position: winner_index as u32 + 1,
+        ticket: entry.first_ticket + ticket_offset,
+        order_index: entry.order_index,
+        ticket_offset,
+        pct: *pct,
+      };
+      if !config.selection.with_replacement {
+        pool.remove_wallet(&selection.wallet)?;
+      }
+      selections.push(selection);
     }
 
+    Ok(selections)
+  }
+
+  /// Credit the winners of the round with the rewards of each Incentive added
+  /// to it. A reward targeting a position goes to the winner at that position
+  /// or back to its source if there is no such position. Otherwise, it's split
+  /// evenly among all positions, so a wallet drawn more than once receives a
+  /// share for each of its positions.
+  fn distribute_incentives(
+    storage: &mut dyn Storage,
+    round: &Round,
+    selections: &[(Addr, u8)],
+  ) -> Result<(), ContractError> {
+    if selections.is_empty() {
+      return Ok(());
+    }
+    let n_positions = selections.len() as u128;
+    for incentive in INCENTIVES
+      .may_load(storage, round.index)?
+      .unwrap_or_default()
+      .iter()
+    {
+      for reward in incentive.rewards.iter() {
+        if let Some(t) = &reward.token {
+          if let Some(position) = reward.position {
+            let recipient = match selections.get((position as usize).wrapping_sub(1)) {
+              Some((wallet, _)) => wallet,
+              None => &incentive.source,
+            };
+            Self::upsert_claim(storage, recipient, &t.token, t.amount, None)?;
+          } else {
+            // any remainder left over from rounding goes to the 1st place winner
+            let amount = t.amount.multiply_ratio(1u128, n_positions);
+            let remainder = t.amount - amount * Uint128::from(n_positions);
+            for (i, (wallet, _)) in selections.iter().enumerate() {
+              let amount = if i == 0 { amount + remainder } else { amount };
+              Self::upsert_claim(storage, wallet, &t.token, amount, None)?;
+            }
+          }
+        }
+      }
+    }
     Ok(())
   }
 
+  /// Increment the Claim of the given wallet. If the amount was won in a
+  /// round, the round is recorded in the Claim as well.
   fn upsert_claim(
     storage: &mut dyn Storage,
     wallet: &Addr,
+    token: &Token,
     amount: Uint128,
+    winning_round: Option<u32>,
   ) -> Result<(), ContractError> {
     CLAIMS.update(
       storage,
       wallet.clone(),
       |some_claim| -> Result<Claim, ContractError> {
-        if let Some(mut claim) = some_claim {
-          claim.amount += amount;
-          Ok(claim)
-        } else {
-          Ok(Claim {
-            wallet: wallet.clone(),
-            amount,
-          })
+        let mut claim = some_claim.unwrap_or_else(|| Claim::new(wallet));
+        claim.increment(token, amount);
+        if let Some(round_index) = winning_round {
+          if !claim.rounds.contains(&round_index) {
+            claim.rounds.push(round_index);
+          }
         }
+        Ok(claim)
       },
     )?;
     Ok(())
   }
+}
 
-  /// Based on the config params, return a vec containing a pct int (value between
-  /// 0..100), specifying the "claim" percent owed to each winner's wallet
-  /// according to their place -- e.g. 1st place, 2nd place.
-  fn calculate_claim_percentages(
-    config: &Config,
-    round: &Round,
-  ) -> StdResult<Vec<u8>> {
-    match config.selection.method.clone() {
-      WinnerSelectionMethod::Fixed(split) => {
-        // a "fixed" winner count means that the % won by each winning wallet is
-        // specified by a static vec of pcts, like [70, 30], for example.  if
-        // there are fewer wallets available than the split vec, we return a slice
-        // instead of the full vec.
-        let n_winners = std::cmp::min(round.counts.wallets, split.len() as u32);
-        Ok(Vec::from_slice(&split[..n_winners as usize])?)
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    models::{
+      incentive::{Incentive, Reward, TokenAmount},
+      round::{Tier, WinnerSelectionMethod},
+    },
+    random::Pcg64,
+    state::ORDERS,
+    testing::{mock_config, mock_round},
+  };
+  use cosmwasm_std::testing::MockStorage;
+  use std::collections::HashSet;
+
+  /// Return a random selection method along with the number of positions it
+  /// has when there are enough wallets to fill every one of them.
+  fn random_method(rng: &mut Pcg64) -> (WinnerSelectionMethod, Option<u32>) {
+    match rng.gen_range(5) {
+      0 => {
+        let pct = 1 + rng.gen_range(100) as u8;
+        let max = 1 + rng.gen_range(20) as u32;
+        (
+          WinnerSelectionMethod::Percent {
+            pct,
+            max: Some(max),
+          },
+          None,
+        )
       },
-      WinnerSelectionMethod::Percent { pct, max } => {
-        // a "percentage" based winner counts means that the number of drawings is
-        // derived as a percent of the total number of ticket-holding wallets, or
-        // at least 1. If a "max" is defined, then we cap the maximum number of
-        // drawings.
-        let mut n_winners = std::cmp::max(1, round.counts.wallets * (pct as u32) / 100);
-        if let Some(n_max_winner) = max {
-          n_winners = n_winners.max(n_max_winner);
-        }
-        // return a vec of identical percentages for each winner
-        let pct = (100 / n_winners) as u8;
-        Ok(iter::repeat(pct).take(n_winners as usize).collect())
+      1 => {
+        let n = 1 + rng.gen_range(5);
+        let mut split = vec![100 / n as u8; n as usize];
+        split[0] += 100 % n as u8;
+        (WinnerSelectionMethod::Fixed(split), Some(n as u32))
+      },
+      2 => {
+        let ratio = 1 + rng.gen_range(100) as u8;
+        let max = 1 + rng.gen_range(20) as u32;
+        (WinnerSelectionMethod::GeometricDecay { ratio, max }, None)
       },
+      3 => {
+        let tiers = vec![
+          Tier { count: 1, pct: 50 },
+          Tier { count: 5, pct: 5 },
+          Tier { count: 25, pct: 1 },
+        ];
+        (WinnerSelectionMethod::Tiered(tiers), Some(31))
+      },
+      _ => (WinnerSelectionMethod::WinnerTakesAll, Some(1)),
+    }
+  }
+
+  /// Save a round in which each wallet places one or more orders of random
+  /// sizes, interleaved with the orders of other wallets.
+  fn setup_round(
+    storage: &mut dyn Storage,
+    rng: &mut Pcg64,
+    config: &Config,
+    n_wallets: u32,
+  ) -> Round {
+    let n_orders = n_wallets + rng.gen_range(n_wallets as u64 + 1) as u32;
+    let orders: Vec<(u32, u32)> = (0..n_orders)
+      .map(|order_index| {
+        // every wallet places at least one order
+        let wallet_index = if order_index < n_wallets {
+          order_index
+        } else {
+          rng.gen_range(n_wallets as u64) as u32
+        };
+        (wallet_index, 1 + rng.gen_range(10) as u32)
+      })
+      .collect();
+    mock_round(storage, config, &orders)
+  }
+
+  #[test]
+  fn select_winners_fills_each_position_it_can() {
+    let mut rng = Pcg64::from_seed([7; 32]);
+    for case in 0..500 {
+      let (method, n_positions) = random_method(&mut rng);
+      let with_replacement = rng.gen_range(2) == 0;
+      let n_wallets = 1 + rng.gen_range(40) as u32;
+      let config = mock_config(method, with_replacement);
+      let mut storage = MockStorage::new();
+      let round = setup_round(&mut storage, &mut rng, &config, n_wallets);
+      let seed = seed::init(&Addr::unchecked("seed"), case);
+
+      let selections = Lottery::select_winners(&storage, &config, &round, &seed).unwrap();
+      let claim_pcts = selection::claim_percentages(&config.selection.method, n_wallets);
+
+      // exactly one selection per position, of which there are never more
+      // than the number of wallets
+      assert_eq!(selections.len(), claim_pcts.len(), "case {}", case);
+      assert!(selections.len() as u32 <= n_wallets, "case {}", case);
+      if let Some(n_positions) = n_positions {
+        assert_eq!(
+          selections.len() as u32,
+          n_positions.min(n_wallets),
+          "case {}",
+          case
+        );
+      }
+      for (i, selection) in selections.iter().enumerate() {
+        assert_eq!(selection.position, i as u32 + 1);
+        assert_eq!(selection.pct, claim_pcts[i]);
+        let order = ORDERS.load(&storage, (0, selection.order_index)).unwrap();
+        assert_eq!(selection.wallet, order.wallet);
+        assert_eq!(
+          selection.ticket,
+          order.first_ticket + selection.ticket_offset
+        );
+        assert!(selection.ticket <= order.last_ticket());
+      }
+
+      // without replacement, no wallet is selected twice
+      if !with_replacement {
+        let wallets: HashSet<&Addr> = selections.iter().map(|x| &x.wallet).collect();
+        assert_eq!(wallets.len(), selections.len(), "case {}", case);
+      }
+
+      // the draw is a pure function of the round and its seed
+      assert_eq!(
+        Lottery::select_winners(&storage, &config, &round, &seed).unwrap(),
+        selections
+      );
     }
   }
+
+  #[test]
+  fn select_winners_without_replacement_can_select_every_wallet() {
+    let mut rng = Pcg64::from_seed([8; 32]);
+    for case in 0..100 {
+      let n_wallets = 1 + rng.gen_range(30) as u32;
+      let method = WinnerSelectionMethod::Percent {
+        pct: 100,
+        max: None,
+      };
+      let config = mock_config(method, false);
+      let mut storage = MockStorage::new();
+      let round = setup_round(&mut storage, &mut rng, &config, n_wallets);
+      let seed = seed::init(&Addr::unchecked("seed"), case);
+
+      let selections = Lottery::select_winners(&storage, &config, &round, &seed).unwrap();
+      let wallets: HashSet<&Addr> = selections.iter().map(|x| &x.wallet).collect();
+      assert_eq!(wallets.len() as u32, n_wallets.min(100), "case {}", case);
+    }
+  }
+
+  /// Save an incentive from "sponsor" to round 0 with a reward of the given
+  /// amount of native tokens for each (position, amount) pair.
+  fn add_incentive(
+    storage: &mut dyn Storage,
+    rewards: &[(Option<u32>, u128)],
+  ) {
+    let token = mock_config(WinnerSelectionMethod::WinnerTakesAll, false).token;
+    let incentive = Incentive {
+      source: Addr::unchecked("sponsor"),
+      rewards: rewards
+        .iter()
+        .map(|(position, amount)| Reward {
+          token: Some(TokenAmount {
+            token: token.clone(),
+            amount: Uint128::from(*amount),
+          }),
+          position: *position,
+        })
+        .collect(),
+    };
+    INCENTIVES.save(storage, 0, &vec![incentive]).unwrap();
+  }
+
+  fn claimed(
+    storage: &dyn Storage,
+    wallet: &str,
+  ) -> u128 {
+    CLAIMS
+      .may_load(storage, Addr::unchecked(wallet))
+      .unwrap()
+      .map(|claim| claim.balances.iter().map(|x| x.amount.u128()).sum())
+      .unwrap_or_default()
+  }
+
+  fn selections(wallets: &[&str]) -> Vec<(Addr, u8)> {
+    wallets
+      .iter()
+      .map(|wallet| (Addr::unchecked(*wallet), 0))
+      .collect()
+  }
+
+  #[test]
+  fn distribute_incentives_returns_rewards_for_missing_positions_to_their_source() {
+    let mut storage = MockStorage::new();
+    let config = mock_config(WinnerSelectionMethod::WinnerTakesAll, false);
+    let round = mock_round(&mut storage, &config, &[(0, 1), (1, 1)]);
+    add_incentive(&mut storage, &[(Some(1), 10), (Some(2), 20), (Some(0), 40)]);
+
+    Lottery::distribute_incentives(&mut storage, &round, &selections(&["wallet1"])).unwrap();
+
+    // only the 1st position was drawn, so position 2, like the nonexistent
+    // position 0, goes back to the sponsor
+    assert_eq!(claimed(&storage, "wallet1"), 10);
+    assert_eq!(claimed(&storage, "wallet0"), 0);
+    assert_eq!(claimed(&storage, "sponsor"), 60);
+  }
+
+  #[test]
+  fn distribute_incentives_rewards_a_wallet_for_each_of_its_positions() {
+    let mut storage = MockStorage::new();
+    let config = mock_config(WinnerSelectionMethod::WinnerTakesAll, true);
+    let round = mock_round(&mut storage, &config, &[(0, 1), (1, 1)]);
+    add_incentive(&mut storage, &[(Some(1), 100), (Some(3), 7), (None, 30)]);
+
+    // drawn with replacement, wallet0 holds the 1st and 3rd positions
+    let selections = selections(&["wallet0", "wallet1", "wallet0"]);
+    Lottery::distribute_incentives(&mut storage, &round, &selections).unwrap();
+
+    assert_eq!(claimed(&storage, "wallet0"), 100 + 7 + 2 * 10);
+    assert_eq!(claimed(&storage, "wallet1"), 10);
+    assert_eq!(claimed(&storage, "sponsor"), 0);
+  }
+
+  #[test]
+  fn distribute_incentives_gives_the_untargeted_remainder_to_1st_place() {
+    let mut storage = MockStorage::new();
+    let config = mock_config(WinnerSelectionMethod::WinnerTakesAll, false);
+    let round = mock_round(&mut storage, &config, &[(0, 1), (1, 1), (2, 1)]);
+    add_incentive(&mut storage, &[(None, 11)]);
+
+    let selections = selections(&["wallet2", "wallet0", "wallet1"]);
+    Lottery::distribute_incentives(&mut storage, &round, &selections).unwrap();
+
+    // 11 split 3 ways leaves a remainder of 2 for the 1st place winner, so
+    // nothing is lost to rounding
+    assert_eq!(claimed(&storage, "wallet2"), 5);
+    assert_eq!(claimed(&storage, "wallet0"), 3);
+    assert_eq!(claimed(&storage, "wallet1"), 3);
+  }
 }
@@ -1,6 +1,8 @@
 pub mod incentive;
+pub mod jackpot;
 pub mod lottery;
 pub mod player;
+pub mod role;
 pub mod round;
 pub mod royalties;
 pub mod ticket_order;
@@ -0,0 +1,16 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Privileges that the lottery owner can grant to other wallets.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+  // activates the lottery and cancels rounds or the lottery as a whole
+  Operator,
+  // issues refunds to players in canceled rounds
+  Refunder,
+  // pauses and resumes the lottery
+  Pauser,
+  // updates and appends round configs
+  ConfigEditor,
+}
@@ -1,4 +1,6 @@
-use cosmwasm_std::{Addr, Timestamp, Uint128};
+use super::jackpot::JackpotWin;
+use crate::{error::ContractError, selection};
+use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -26,17 +28,122 @@ pub struct RoyaltyRecipient {
   pub pct: u8,
 }
 
+/// Conditions for ending a round, any or all of which must be met depending
+/// on the mode. A round with no conditions never ends on its own.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Targets {
+  // total value of tickets sold
   pub funding_level: Option<Uint128>,
+  // wall-clock time since the round started
   pub duration_minutes: Option<u32>,
+  // block height at which the round ends
+  pub block_height: Option<u64>,
+  // total number of tickets sold
+  pub ticket_count: Option<u32>,
+  // number of ticket-holding wallets
+  pub wallet_count: Option<u32>,
+  // pushes back the end of a timed round when tickets are bought near it
+  pub extension: Option<DeadlineExtension>,
+  // defaults to Any
+  pub mode: Option<TargetMode>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetMode {
+  // end the round once any condition is met
+  Any,
+  // end the round once every condition is met
+  All,
+}
+
+/// Extends the end of a timed round by extension_minutes whenever tickets are
+/// bought within window_minutes of it, up to max_minutes in total.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DeadlineExtension {
+  pub window_minutes: u32,
+  pub extension_minutes: u32,
+  pub max_minutes: u32,
+}
+
+/// Progress of a round toward one of its end conditions.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TargetProgress {
+  pub target: Uint128,
+  pub current: Uint128,
+  pub is_met: bool,
+}
+
+impl TargetProgress {
+  fn new(
+    target: impl Into<Uint128>,
+    current: impl Into<Uint128>,
+  ) -> Self {
+    let target = target.into();
+    let current = current.into();
+    Self {
+      target,
+      current,
+      is_met: current >= target,
+    }
+  }
+}
+
+/// Progress of a round toward each end condition configured for it. Duration
+/// is measured in seconds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundProgress {
+  pub mode: TargetMode,
+  pub funding_level: Option<TargetProgress>,
+  pub duration: Option<TargetProgress>,
+  pub block_height: Option<TargetProgress>,
+  pub ticket_count: Option<TargetProgress>,
+  pub wallet_count: Option<TargetProgress>,
+}
+
+impl RoundProgress {
+  /// Return true if the round's end conditions are satisfied.
+  pub fn is_met(&self) -> bool {
+    let conditions: Vec<&TargetProgress> = [
+      &self.funding_level,
+      &self.duration,
+      &self.block_height,
+      &self.ticket_count,
+      &self.wallet_count,
+    ]
+    .iter()
+    .filter_map(|x| x.as_ref())
+    .collect();
+    if conditions.is_empty() {
+      return false;
+    }
+    match self.mode {
+      TargetMode::Any => conditions.iter().any(|x| x.is_met),
+      TargetMode::All => conditions.iter().all(|x| x.is_met),
+    }
+  }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum WinnerSelectionMethod {
+  // an equal split among a pct of the wallets in the round
   Percent { pct: u8, max: Option<u32> },
+  // a static split of pcts by position
   Fixed(Vec<u8>),
+  // each position wins ratio pct of the position before it
+  GeometricDecay { ratio: u8, max: u32 },
+  // consecutive bands of winners with the same pct
+  Tiered(Vec<Tier>),
+  // a single winner takes the entire pot
+  WinnerTakesAll,
+}
+
+/// A band of winners in a tiered payout, each winning the same pct.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Tier {
+  pub count: u32,
+  pub pct: u8,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -45,6 +152,53 @@ pub struct WinnerSelection {
   pub with_replacement: bool,
 }
 
+/// What to do when a round ends without meeting its minimum participation,
+/// including when it has only a single player.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdPolicy {
+  // cancel the round, making the players' tickets refundable and returning
+  // incentives to their sources
+  Refund,
+  // keep the round open, restarting its duration
+  Extend,
+  // end the round without drawing, carrying its pot and incentives into the
+  // next round. falls back to Refund if there's no next round with the same
+  // ticket token.
+  Rollover,
+}
+
+/// Progressive jackpot settings. Each drawn round contributes pct of its pot to
+/// the lottery's jackpot reserve, and the reserve is won by a separately drawn
+/// ticket with a 1 in odds chance per round.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JackpotConfig {
+  pub pct: u8,
+  pub odds: u32,
+}
+
+/// Source of randomness mixed into the seed used to draw winners, in addition
+/// to the seed derived from ticket orders.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RandomnessSource {
+  // the operator commits to a secret before the round opens and reveals it
+  // to draw winners within the timeout after the round closes.
+  CommitReveal { reveal_timeout_minutes: u32 },
+  // anyone submits the signature of the drand mainnet beacon published after
+  // the round closes, verified by the contract before drawing winners.
+  Drand {},
+  // randomness is requested from a proxy contract when the round closes and
+  // received in a callback from it, which draws the winners. players are
+  // refunded if it doesn't arrive within the timeout.
+  Proxy { address: Addr, timeout_minutes: u32 },
+  // anyone draws winners within window blocks of the block height delay blocks
+  // after the round closes, mixing in data from the block of the draw, so that
+  // the buyer who closes the round can't predict the outcome. players are
+  // refunded if no one draws the round in time.
+  FutureBlock { delay: u64, window: u64 },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
   pub name: Option<String>,
@@ -54,6 +208,51 @@ pub struct Config {
   pub ticket_price: Uint128,
   pub max_tickets_per_wallet: Option<u32>,
   pub royalties: Vec<RoyaltyRecipient>,
+  // minimum number of wallets needed to draw, never less than 2
+  pub min_wallets: Option<u32>,
+  // minimum pot size needed to draw
+  pub min_pot: Option<Uint128>,
+  // defaults to Refund
+  pub threshold_policy: Option<ThresholdPolicy>,
+  pub randomness: Option<RandomnessSource>,
+  pub jackpot: Option<JackpotConfig>,
+}
+
+impl Config {
+  pub fn validate(&self) -> Result<(), ContractError> {
+    if self.ticket_price.is_zero() {
+      return Err(ContractError::ValidationError {
+        reason: Some("ticket price must be greater than 0".to_owned()),
+      });
+    }
+    if self.targets.extension.is_some() && self.targets.duration_minutes.is_none() {
+      return Err(ContractError::ValidationError {
+        reason: Some("deadline extension requires a duration".to_owned()),
+      });
+    }
+    if matches!(self.randomness, Some(RandomnessSource::FutureBlock { window, .. }) if window == 0)
+    {
+      return Err(ContractError::ValidationError {
+        reason: Some("draw window must be at least 1 block".to_owned()),
+      });
+    }
+    let jackpot_pct = self
+      .jackpot
+      .as_ref()
+      .map(|x| x.pct as u32)
+      .unwrap_or_default();
+    if self.royalties.iter().map(|x| x.pct as u32).sum::<u32>() + jackpot_pct > 100 {
+      return Err(ContractError::ValidationError {
+        reason: Some("royalty and jackpot pcts cannot exceed 100".to_owned()),
+      });
+    }
+    if matches!(self.jackpot, Some(ref x) if x.odds == 0) {
+      return Err(ContractError::ValidationError {
+        reason: Some("jackpot odds must be greater than 0".to_owned()),
+      });
+    }
+    selection::validate(&self.selection.method)
+  }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -69,8 +268,26 @@ pub struct Round {
   pub status: RoundStatus,
   pub counts: Counts,
   pub started_at: Option<Timestamp>,
+  // pot carried over from previous rounds that were rolled over
+  #[serde(default)]
+  pub carryover: Uint128,
+  // indices of the rolled-over rounds whose pots make up the carryover
+  #[serde(default)]
+  pub carryover_from: Vec<u32>,
+  // jackpot won in the round, if any
+  pub jackpot: Option<JackpotWin>,
+  // end of the round's duration, once extended past its configured duration
+  pub ends_at: Option<Timestamp>,
+  pub closed_at: Option<Timestamp>,
+  pub drand_round: Option<u64>,
+  pub draw_height: Option<u64>,
   pub ended_by: Option<Addr>,
   pub index: u32,
+  // config resolved for the round when it was created, so that later changes
+  // to the lottery's configs don't affect it
+  pub config: Option<Config>,
+  // index of the lottery config the round was created with
+  pub config_index: Option<u32>,
 }
 
 impl Round {
@@ -78,9 +295,20 @@ impl Round {
     started_at: Timestamp,
     is_active: bool,
     index: u32,
+    config_index: u32,
+    config: &Config,
   ) -> Self {
     Self {
+      config: Some(config.clone()),
+      config_index: Some(config_index),
       ended_by: None,
+      carryover: Uint128::zero(),
+      carryover_from: vec![],
+      jackpot: None,
+      ends_at: None,
+      closed_at: None,
+      drand_round: None,
+      draw_height: None,
       started_at: if is_active { Some(started_at) } else { None },
       index,
       status: if is_active {
@@ -101,25 +329,147 @@ impl Round {
     self.status == RoundStatus::Active
   }
 
+  /// Make a pending round active, starting its duration at the given time.
+  pub fn start(
+    &mut self,
+    time: Timestamp,
+  ) {
+    self.status = RoundStatus::Active;
+    self.restart(time);
+  }
+
+  /// Restart the round's duration from the given time, dropping any extension.
+  pub fn restart(
+    &mut self,
+    time: Timestamp,
+  ) {
+    self.started_at = Some(time);
+    self.ends_at = None;
+  }
+
+  /// Return the effective end time of a timed round, including extensions.
+  pub fn get_end_time(
+    &self,
+    config: &Config,
+  ) -> Option<Timestamp> {
+    match (self.started_at, config.targets.duration_minutes) {
+      (Some(started_at), Some(duration_minutes)) => Some(
+        self
+          .ends_at
+          .unwrap_or_else(|| started_at.plus_seconds((duration_minutes as u64) * 60)),
+      ),
+      _ => None,
+    }
+  }
+
+  /// Push back the end of a timed round if tickets are bought within the
+  /// configured window before it, up to the maximum total extension.
+  pub fn extend_deadline(
+    &mut self,
+    config: &Config,
+    time: Timestamp,
+  ) {
+    let extension = match &config.targets.extension {
+      Some(extension) => extension,
+      None => return,
+    };
+    if let (Some(started_at), Some(duration_minutes), Some(ends_at)) = (
+      self.started_at,
+      config.targets.duration_minutes,
+      self.get_end_time(config),
+    ) {
+      let window_start = ends_at.minus_seconds((extension.window_minutes as u64) * 60);
+      if time >= window_start && time < ends_at {
+        let max_ends_at = started_at
+          .plus_seconds(((duration_minutes as u64) + (extension.max_minutes as u64)) * 60);
+        let extended = ends_at.plus_seconds((extension.extension_minutes as u64) * 60);
+        self.ends_at = Some(extended.min(max_ends_at));
+      }
+    }
+  }
+
   pub fn is_canceled(&self) -> bool {
     self.status == RoundStatus::Canceled
   }
 
+  pub fn is_closed(&self) -> bool {
+    self.status == RoundStatus::Closed
+  }
+
+  /// Return true if the round is closed and the randomness needed to draw its
+  /// winners has not arrived before its deadline.
+  pub fn is_draw_expired(
+    &self,
+    config: &Config,
+    block: &BlockInfo,
+  ) -> bool {
+    if !self.is_closed() {
+      return false;
+    }
+    match (&config.randomness, self.closed_at, self.draw_height) {
+      (
+        Some(RandomnessSource::CommitReveal {
+          reveal_timeout_minutes,
+        }),
+        Some(closed_at),
+        _,
+      ) => block.time > closed_at.plus_seconds((*reveal_timeout_minutes as u64) * 60),
+      (
+        Some(RandomnessSource::Proxy {
+          timeout_minutes, ..
+        }),
+        Some(closed_at),
+        _,
+      ) => block.time > closed_at.plus_seconds((*timeout_minutes as u64) * 60),
+      (Some(RandomnessSource::FutureBlock { window, .. }), _, Some(draw_height)) => {
+        block.height > draw_height + window
+      },
+      _ => false,
+    }
+  }
+
   pub fn should_end(
     &self,
     config: &Config,
-    block_time: Timestamp,
+    block: &BlockInfo,
   ) -> bool {
-    if let Some(funding_level) = config.targets.funding_level {
-      Uint128::from(self.counts.tickets) * config.ticket_price >= funding_level
-    } else if let Some(minute_duration) = config.targets.duration_minutes {
-      if let Some(started_at) = self.started_at {
-        block_time >= started_at.plus_seconds((minute_duration as u64) * 60)
-      } else {
-        false
-      }
-    } else {
-      false
+    self.get_progress(config, block).is_met()
+  }
+
+  /// Return the progress of the round toward each of its end conditions.
+  pub fn get_progress(
+    &self,
+    config: &Config,
+    block: &BlockInfo,
+  ) -> RoundProgress {
+    let targets = &config.targets;
+    // a round that hasn't started makes no progress toward its duration
+    let elapsed_seconds = self
+      .started_at
+      .map(|started_at| block.time.seconds().saturating_sub(started_at.seconds()))
+      .unwrap_or_default();
+    // the duration includes any extension of the round's end time
+    let duration_seconds = match (self.started_at, self.get_end_time(config)) {
+      (Some(started_at), Some(ends_at)) => ends_at.seconds() - started_at.seconds(),
+      _ => targets.duration_minutes.unwrap_or_default() as u64 * 60,
+    };
+    RoundProgress {
+      mode: targets.mode.clone().unwrap_or(TargetMode::Any),
+      funding_level: targets
+        .funding_level
+        .map(|funding_level| TargetProgress::new(funding_level, self.get_pot_size(config))),
+      duration: targets
+        .duration_minutes
+        .map(|_| TargetProgress::new(duration_seconds, elapsed_seconds)),
+      block_height: targets
+        .block_height
+        .map(|block_height| TargetProgress::new(block_height, block.height)),
+      ticket_count: targets
+        .ticket_count
+        .map(|ticket_count| TargetProgress::new(ticket_count, self.counts.tickets)),
+      wallet_count: targets
+        .wallet_count
+        .map(|wallet_count| TargetProgress::new(wallet_count, self.counts.wallets)),
     }
   }
 
@@ -127,7 +477,18 @@ impl Round {
     &self,
     config: &Config,
   ) -> Uint128 {
-    Uint128::from(self.counts.tickets) * Uint128::from(config.ticket_price)
+    Uint128::from(self.counts.tickets) * config.ticket_price + self.carryover
+  }
+
+  /// Return true if the round has enough wallets and a large enough pot to be
+  /// drawn.
+  pub fn meets_minimums(
+    &self,
+    config: &Config,
+  ) -> bool {
+    let min_wallets = config.min_wallets.unwrap_or_default().max(2);
+    let min_pot = config.min_pot.unwrap_or_default();
+    self.counts.wallets >= min_wallets && self.get_pot_size(config) >= min_pot
   }
 
   pub fn get_total_royalty_amount(
@@ -2,8 +2,39 @@ use cosmwasm_std::{Addr, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::{
+  incentive::{increment_balance, TokenAmount},
+  round::Token,
+};
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Claim {
   pub wallet: Addr,
-  pub amount: Uint128,
+  // claimable balance of each token owed to the wallet
+  pub balances: Vec<TokenAmount>,
+  // indices of rounds with Winner records included in the claim balances
+  pub rounds: Vec<u32>,
+}
+
+impl Claim {
+  pub fn new(wallet: &Addr) -> Self {
+    Self {
+      wallet: wallet.clone(),
+      balances: vec![],
+      rounds: vec![],
+    }
+  }
+
+  /// Increment the claimable balance of the given token.
+  pub fn increment(
+    &mut self,
+    token: &Token,
+    amount: Uint128,
+  ) {
+    increment_balance(&mut self.balances, token, amount);
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.balances.iter().all(|x| x.amount.is_zero())
+  }
 }
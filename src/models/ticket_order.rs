@@ -5,7 +5,22 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TicketOrder {
   pub wallet: Addr,
+  // number of the first ticket in the order's contiguous range of tickets,
+  // where ticket numbers start at 1 and are never reused within a round.
+  pub first_ticket: u32,
   pub ticket_count: u32,
   pub message: Option<String>,
   pub is_public: bool,
 }
+
+impl TicketOrder {
+  /// Number of the order's last ticket.
+  pub fn last_ticket(&self) -> u32 {
+    self.first_ticket + self.ticket_count - 1
+  }
+
+  /// Number of the first ticket in the order that follows this one.
+  pub fn next_ticket(&self) -> u32 {
+    self.first_ticket + self.ticket_count
+  }
+}
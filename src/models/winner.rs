@@ -7,5 +7,25 @@ pub struct Winner {
   pub wallet: Addr,
   pub amount_total: Uint128,
   pub amount_claimed: Uint128,
-  pub position: u16,
+  // place of the winner in the draw (1st, 2nd, etc.)
+  pub position: u32,
+  // number of the winning ticket within the round
+  pub ticket: u32,
+  // index of the TicketOrder containing the winning ticket
+  pub order_index: u32,
+  // offset of the winning ticket within its TicketOrder
+  pub ticket_offset: u32,
+}
+
+/// A ticket drawn for a position in a round, prior to being recorded as a
+/// Winner.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Selection {
+  pub wallet: Addr,
+  pub position: u32,
+  pub ticket: u32,
+  pub order_index: u32,
+  pub ticket_offset: u32,
+  // pct of the round's winnings won by the position
+  pub pct: u8,
 }
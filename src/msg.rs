@@ -1,8 +1,8 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, Timestamp};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::models::{incentive::Reward, round::Config};
+use crate::models::{incentive::Reward, role::Role, round::Config};
 
 /// Initial contract state.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -11,6 +11,7 @@ pub struct InstantiateMsg {
   pub rounds: InitialRounds,
   pub tournament: Option<bool>,
   pub activate: Option<bool>,
+  pub start_at: Option<Timestamp>,
 }
 
 /// Initial contract state.
@@ -23,6 +24,7 @@ pub struct InitialRounds {
 /// Executable contract endpoints.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
   BuyTickets {
     count: u32,
@@ -36,18 +38,80 @@ pub enum ExecuteMsg {
     round: u32,
     recipient: Addr,
   },
+  ClaimRefund {
+    round: u32,
+  },
+  CancelRound {
+    index: u32,
+  },
+  CancelLottery {},
+  UpdateConfig {
+    index: u32,
+    config: Config,
+  },
+  AppendRounds {
+    configs: Vec<Config>,
+    count: Option<u32>,
+  },
+  Activate {},
+  Pause {},
+  Resume {},
+  ProposeOwner {
+    owner: Addr,
+  },
+  AcceptOwner {},
+  RenounceOwnership {},
+  GrantRole {
+    wallet: Addr,
+    role: Role,
+  },
+  RevokeRole {
+    wallet: Addr,
+    role: Role,
+  },
+  CommitSecret {
+    round: u32,
+    commitment: String,
+  },
+  RevealSecret {
+    round: u32,
+    secret: String,
+  },
+  SubmitBeacon {
+    round: u32,
+    signature: Binary,
+    previous_signature: Binary,
+  },
+  ReceiveRandomness {
+    job_id: String,
+    randomness: Binary,
+  },
+  Draw {
+    round: u32,
+  },
   ClaimRewards {},
   TerminateRound {},
 }
 
+/// Contract migration parameters.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 /// Custom contract query endpoints.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+  GetLottery {},
   GetRound {
     index: u32,
     players: Option<bool>,
     winners: Option<bool>,
     orders: Option<bool>,
   },
+  GetRoundProgress {
+    index: u32,
+  },
+  VerifyDraw {
+    round: u32,
+  },
 }
@@ -0,0 +1,25 @@
+use cosmwasm_std::Deps;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  error::ContractError,
+  models::{incentive::TokenAmount, lottery::Lottery},
+  state::{JACKPOT, LOTTERY},
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetLotteryResponse {
+  lottery: Lottery,
+  // current balances of the jackpot reserve
+  jackpot: Vec<TokenAmount>,
+}
+
+pub fn get_lottery(deps: Deps) -> Result<GetLotteryResponse, ContractError> {
+  let lottery: Lottery = LOTTERY.load(deps.storage)?;
+  let jackpot = JACKPOT.may_load(deps.storage)?.unwrap_or_default();
+  Ok(GetLotteryResponse {
+    lottery,
+    jackpot: jackpot.balances,
+  })
+}
@@ -1,28 +1,41 @@
-use cosmwasm_std::{Addr, Deps, Order, Timestamp};
+use cosmwasm_std::{Addr, Deps, Order, Timestamp, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
   error::ContractError,
   models::{
+    incentive::TokenAmount,
+    jackpot::JackpotWin,
     lottery::Lottery,
     player::Player,
     round::{Config, Counts, RoundStatus},
     ticket_order::TicketOrder,
     winner::Winner,
   },
-  state::{LOTTERY, ORDERS, PLAYERS, ROUNDS, WINNERS},
+  state::{JACKPOT, LOTTERY, ORDERS, PLAYERS, ROUNDS, WINNERS},
 };
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GetRoundResponse {
   config: Config,
+  // index of the lottery config the round was created with
+  config_index: u32,
   players: Option<Vec<Player>>,
   winners: Option<Vec<Winner>>,
   orders: Option<Vec<TicketOrder>>,
   status: RoundStatus,
   counts: Counts,
+  carryover: Uint128,
+  // jackpot won in the round, if any
+  jackpot_win: Option<JackpotWin>,
+  // current balances of the lottery's jackpot reserve
+  jackpot: Vec<TokenAmount>,
   started_at: Option<Timestamp>,
+  ends_at: Option<Timestamp>,
+  closed_at: Option<Timestamp>,
+  drand_round: Option<u64>,
+  draw_height: Option<u64>,
   ended_by: Option<Addr>,
 }
 
@@ -39,8 +52,8 @@ pub fn get_round(
     return Err(ContractError::RoundNotFound {});
   }
 
-  let config = &lottery.rounds.configs[(round_index as usize) % (lottery.rounds.configs.len())];
   let round = ROUNDS.load(deps.storage, round_index)?;
+  let config = lottery.get_round_config(&round);
 
   let players: Option<Vec<Player>> = if include_players.unwrap_or(false) {
     Some(
@@ -58,7 +71,7 @@ pub fn get_round(
     Some(
       WINNERS
         .prefix(round_index)
-        .range(deps.storage, None, None, Order::Descending)
+        .range(deps.storage, None, None, Order::Ascending)
         .map(|entry| entry.unwrap().1)
         .collect(),
     )
@@ -78,12 +91,22 @@ pub fn get_round(
     None
   };
 
+  let ends_at = round.get_end_time(config);
+
   Ok(GetRoundResponse {
     config: config.clone(),
+    config_index: lottery.get_round_config_index(&round) as u32,
     status: round.status,
     started_at: round.started_at,
+    ends_at,
+    closed_at: round.closed_at,
+    drand_round: round.drand_round,
+    draw_height: round.draw_height,
     ended_by: round.ended_by,
     counts: round.counts,
+    carryover: round.carryover,
+    jackpot_win: round.jackpot,
+    jackpot: JACKPOT.may_load(deps.storage)?.unwrap_or_default().balances,
     players,
     winners,
     orders,
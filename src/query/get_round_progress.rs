@@ -0,0 +1,25 @@
+use cosmwasm_std::{Deps, Env};
+
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, round::RoundProgress},
+  state::{LOTTERY, ROUNDS},
+};
+
+/// Return the progress of a round toward each of its end conditions.
+pub fn get_round_progress(
+  deps: Deps,
+  env: &Env,
+  round_index: u32,
+) -> Result<RoundProgress, ContractError> {
+  let lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  if round_index > lottery.rounds.index {
+    return Err(ContractError::RoundNotFound {});
+  }
+
+  let round = ROUNDS.load(deps.storage, round_index)?;
+  let config = lottery.get_round_config(&round);
+
+  Ok(round.get_progress(config, &env.block))
+}
@@ -1 +1,4 @@
+pub mod get_lottery;
 pub mod get_round;
+pub mod get_round_progress;
+pub mod verify_draw;
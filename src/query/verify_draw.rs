@@ -0,0 +1,51 @@
+use cosmwasm_std::{Addr, Deps};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  error::ContractError,
+  models::{lottery::Lottery, winner::Selection},
+  state::{LOTTERY, ROUNDS, ROUND_SEEDS},
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VerifyDrawResponse {
+  round: u32,
+  seed: String,
+  selections: Vec<Selection>,
+  // wallet and ticket number that won the jackpot, if it was won
+  jackpot_winner: Option<(Addr, u32)>,
+}
+
+/// Replay the draw of a completed round from its final seed, ticket orders and
+/// config, returning the selected tickets by position. These should match the
+/// round's recorded winners.
+pub fn verify_draw(
+  deps: Deps,
+  round_index: u32,
+) -> Result<VerifyDrawResponse, ContractError> {
+  let lottery: Lottery = LOTTERY.load(deps.storage)?;
+
+  if round_index > lottery.rounds.index {
+    return Err(ContractError::RoundNotFound {});
+  }
+
+  let round = ROUNDS.load(deps.storage, round_index)?;
+  let config = lottery.get_round_config(&round);
+
+  // only rounds that have been drawn have a final seed
+  let seed = ROUND_SEEDS
+    .may_load(deps.storage, round_index)?
+    .ok_or(ContractError::RoundNotReady {})?;
+
+  let selections = Lottery::select_winners(deps.storage, config, &round, &seed)?;
+  let forced = matches!(round.jackpot, Some(ref x) if x.forced);
+  let jackpot_winner = Lottery::select_jackpot_winner(deps.storage, config, &round, &seed, forced)?;
+
+  Ok(VerifyDrawResponse {
+    round: round_index,
+    seed,
+    selections,
+    jackpot_winner,
+  })
+}
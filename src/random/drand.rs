@@ -0,0 +1,122 @@
+/// Verification of beacons published by the League of Entropy drand mainnet,
+/// whose signatures are chained and verified against its public key on the
+/// BLS12-381 G1 curve.
+///
+/// See: https://drand.love/developer/http-api/#info.
+use cosmwasm_std::Timestamp;
+use drand_verify::{derive_randomness, G1Pubkey, Pubkey};
+
+use crate::error::ContractError;
+
+/// Public key of the League of Entropy mainnet.
+const PK_LEO_MAINNET: [u8; 48] = [
+  0x86, 0x8f, 0x00, 0x5e, 0xb8, 0xe6, 0xe4, 0xca, 0x0a, 0x47, 0xc8, 0xa7, 0x7c, 0xea, 0xa5, 0x30,
+  0x9a, 0x47, 0x97, 0x8a, 0x7c, 0x71, 0xbc, 0x5c, 0xce, 0x96, 0x36, 0x6b, 0x5d, 0x7a, 0x56, 0x99,
+  0x37, 0xc5, 0x29, 0xee, 0xda, 0x66, 0xc7, 0x29, 0x37, 0x84, 0xa9, 0x40, 0x28, 0x01, 0xaf, 0x31,
+];
+
+/// Unix time at which the first mainnet round was published.
+const GENESIS_TIME: u64 = 1595431050;
+
+/// Seconds between consecutive mainnet rounds.
+const PERIOD: u64 = 30;
+
+/// Number of rounds to skip past the first round published after a given time.
+/// Block time can run ahead of wall-clock time, so without a margin, a beacon
+/// could already be public by the time its round is assigned.
+const SAFETY_MARGIN: u64 = 3;
+
+/// Return the drand round to draw with, published a safety margin of rounds
+/// after the first round published strictly after the given time.
+pub fn next_round(time: Timestamp) -> u64 {
+  let first_round_after = if time.seconds() < GENESIS_TIME {
+    1
+  } else {
+    (time.seconds() - GENESIS_TIME) / PERIOD + 2
+  };
+  first_round_after + SAFETY_MARGIN
+}
+
+/// Verify the signature of a drand round, returning the randomness derived
+/// from it.
+pub fn verify(
+  round: u64,
+  previous_signature: &[u8],
+  signature: &[u8],
+) -> Result<[u8; 32], ContractError> {
+  let pk = G1Pubkey::from_fixed(PK_LEO_MAINNET).map_err(|_| ContractError::InvalidBeacon {})?;
+  match pk.verify(round, previous_signature, signature) {
+    Ok(true) => Ok(derive_randomness(signature)),
+    _ => Err(ContractError::InvalidBeacon {}),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // recorded from https://drand.cloudflare.com/public/72785
+  const ROUND: u64 = 72785;
+  const PREVIOUS_SIGNATURE: &str = "a609e19a03c2fcc559e8dae14900aaefe517cb55c840f6e69bc8e4f66c8d18e8a609685d9917efbfb0c37f058c2de88f13d297c7e19e0ab24813079efe57a182554ff054c7638153f9b26a60e7111f71a0ff63d9571704905d3ca6df0b031747";
+  const SIGNATURE: &str = "82f5d3d2de4db19d40a6980e8aa37842a0e55d1df06bd68bddc8d60002e8e959eb9cfa368b3c1b77d18f02a54fe047b80f0989315f83b12a74fd8679c4f12aae86eaf6ab5690b34f1fddd50ee3cc6f6cdf59e95526d5a5d82aaa84fa6f181e42";
+  const RANDOMNESS: &str = "8b676484b5fb1f37f9ec5c413d7d29883504e5b669f604a1ce68b3388e9ae3d9";
+
+  /// Unix time at which the given round was published.
+  fn published_at(round: u64) -> u64 {
+    GENESIS_TIME + (round - 1) * PERIOD
+  }
+
+  #[test]
+  fn verifies_recorded_beacon() {
+    let previous_signature = hex::decode(PREVIOUS_SIGNATURE).unwrap();
+    let signature = hex::decode(SIGNATURE).unwrap();
+    let randomness = verify(ROUND, &previous_signature, &signature).unwrap();
+    assert_eq!(hex::encode(randomness), RANDOMNESS);
+  }
+
+  #[test]
+  fn rejects_tampered_beacons() {
+    let previous_signature = hex::decode(PREVIOUS_SIGNATURE).unwrap();
+    let signature = hex::decode(SIGNATURE).unwrap();
+
+    // signature of another round
+    assert!(verify(ROUND + 1, &previous_signature, &signature).is_err());
+
+    // altered signature
+    let mut tampered = signature.clone();
+    tampered[95] ^= 1;
+    assert!(verify(ROUND, &previous_signature, &tampered).is_err());
+
+    // altered previous signature
+    let mut tampered = previous_signature.clone();
+    tampered[95] ^= 1;
+    assert!(verify(ROUND, &tampered, &signature).is_err());
+
+    // truncated and empty signatures
+    assert!(verify(ROUND, &previous_signature, &signature[..95]).is_err());
+    assert!(verify(ROUND, &previous_signature, &[]).is_err());
+  }
+
+  #[test]
+  fn next_round_at_period_boundaries() {
+    let at = |seconds: u64| next_round(Timestamp::from_seconds(seconds));
+    // round 1 is published at genesis, so round 2 is the first one after it
+    assert_eq!(at(GENESIS_TIME), 2 + SAFETY_MARGIN);
+    assert_eq!(at(GENESIS_TIME + PERIOD - 1), 2 + SAFETY_MARGIN);
+    assert_eq!(at(GENESIS_TIME + PERIOD), 3 + SAFETY_MARGIN);
+    assert_eq!(at(GENESIS_TIME + PERIOD + 1), 3 + SAFETY_MARGIN);
+    assert_eq!(at(0), 1 + SAFETY_MARGIN);
+    assert_eq!(at(GENESIS_TIME - 1), 1 + SAFETY_MARGIN);
+    assert_eq!(at(published_at(ROUND)), ROUND + 1 + SAFETY_MARGIN);
+  }
+
+  #[test]
+  fn next_round_is_published_after_the_margin() {
+    for seconds in GENESIS_TIME..GENESIS_TIME + 10 * PERIOD {
+      let round = next_round(Timestamp::from_seconds(seconds));
+      assert!(published_at(round) > seconds + SAFETY_MARGIN * PERIOD);
+      // and no later than necessary
+      assert!(published_at(round - 1) <= seconds + SAFETY_MARGIN * PERIOD);
+    }
+  }
+}
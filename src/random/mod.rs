@@ -1,12 +1,16 @@
 use crate::error::ContractError;
 use base64ct::{Base64, Encoding};
 
+pub mod drand;
 mod pcg64;
+pub mod proxy;
 pub mod seed;
+mod ticket_pool;
 
 pub use pcg64::Pcg64;
+pub use ticket_pool::TicketPool;
 
-pub fn pcg64_from_seed(seed: &String) -> Result<Pcg64, ContractError> {
+pub fn pcg64_from_seed(seed: &str) -> Result<Pcg64, ContractError> {
   match Base64::decode_vec(seed) {
     Ok(bytes_vec) => {
       let mut bytes = [0u8; 32];
@@ -31,6 +31,23 @@ impl Pcg64 {
     output_xsl_rr(self.state)
   }
 
+  /// Return a uniformly distributed integer in 0..n, rejecting outputs that
+  /// would otherwise bias the result toward smaller values. Panics if n is 0.
+  pub fn gen_range(
+    &mut self,
+    n: u64,
+  ) -> u64 {
+    // outputs below the threshold are discarded, leaving a range of outputs
+    // whose length is a multiple of n. the threshold is (2^64 - n) % n.
+    let threshold = n.wrapping_neg() % n;
+    loop {
+      let x = self.next_u64();
+      if x >= threshold {
+        return x % n;
+      }
+    }
+  }
+
   #[inline]
   fn from_state_incr(
     state: u128,
@@ -0,0 +1,41 @@
+/// Randomness requested from an external proxy contract, which responds with
+/// a callback execution carrying the randomness for the requested job.
+use cosmwasm_std::{to_binary, Addr, SubMsg, WasmMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+
+const JOB_ID_PREFIX: &str = "round-";
+
+/// Execute message sent to the proxy contract to request randomness.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyExecuteMsg {
+  GetNextRandomness { job_id: String },
+}
+
+/// Return the ID of the job requesting randomness to draw the given round.
+pub fn job_id(round_index: u32) -> String {
+  format!("{}{}", JOB_ID_PREFIX, round_index)
+}
+
+/// Return the index of the round drawn with the randomness of the given job.
+pub fn parse_job_id(job_id: &str) -> Option<u32> {
+  job_id.strip_prefix(JOB_ID_PREFIX)?.parse().ok()
+}
+
+/// Build a submsg that requests randomness from the proxy contract for
+/// drawing the given round.
+pub fn build_request_submsg(
+  proxy_address: &Addr,
+  round_index: u32,
+) -> Result<SubMsg, ContractError> {
+  Ok(SubMsg::new(WasmMsg::Execute {
+    contract_addr: proxy_address.clone().into(),
+    msg: to_binary(&ProxyExecuteMsg::GetNextRandomness {
+      job_id: job_id(round_index),
+    })?,
+    funds: vec![],
+  }))
+}
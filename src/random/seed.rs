@@ -1,5 +1,5 @@
 use base64ct::{Base64, Encoding};
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, BlockInfo};
 use sha2::{Digest, Sha256};
 
 pub fn init(
@@ -32,6 +32,38 @@ pub fn update(
   Base64::encode_string(&hash)
 }
 
+pub fn mix(
+  seed: &String,
+  entropy: &[u8],
+) -> String {
+  let mut sha256 = Sha256::new();
+  sha256.update(seed.as_bytes());
+  sha256.update(entropy);
+  let hash = sha256.finalize();
+  Base64::encode_string(&hash)
+}
+
+/// Return entropy derived from the block in which a round is drawn. None of it
+/// comes from the caller, whose only choice is which block within the round's
+/// draw window to submit the draw in, without knowing that block's time ahead
+/// of it. This isn't hidden from validators, though: the proposer of each block
+/// in the window sets its time within the bounds allowed by consensus, can
+/// compute the outcome before proposing, and can leave the draw out of its
+/// block, so a proposer colluding with a player can pick among outcomes.
+pub fn block_entropy(block: &BlockInfo) -> Vec<u8> {
+  let mut sha256 = Sha256::new();
+  sha256.update(block.height.to_le_bytes());
+  sha256.update(block.time.nanos().to_le_bytes());
+  sha256.update(block.chain_id.as_bytes());
+  sha256.finalize().to_vec()
+}
+
+/// Return the commitment to a secret, revealed later on to draw a round.
+pub fn commit(secret: &str) -> String {
+  let hash = Sha256::digest(secret.as_bytes());
+  Base64::encode_string(&hash)
+}
+
 pub fn finalize(
   seed: &String,
   sender: &Addr,
@@ -0,0 +1,262 @@
+use cosmwasm_std::{Addr, Order, StdError, Storage};
+use cw_storage_plus::Bound;
+
+use super::Pcg64;
+use crate::{
+  error::ContractError,
+  state::{ORDERS, PLAYERS, TICKETS},
+};
+
+/// The ticket order containing a drawn ticket.
+pub struct PoolEntry {
+  pub order_index: u32,
+  pub wallet: Addr,
+  // number of the order's first ticket within the round
+  pub first_ticket: u32,
+}
+
+/// The tickets in a round, drawn from directly in storage. Tickets in a round
+/// that can be drawn are numbered contiguously from 1, since players are only
+/// ever removed from canceled rounds, so a drawn ticket is found by looking up
+/// the order with the greatest first ticket at or below it in the TICKETS
+/// index. The cost of a draw is independent of the number of tickets in the
+/// round, growing only with the number of orders removed from the pool.
+pub struct TicketPool<'a> {
+  storage: &'a dyn Storage,
+  round_index: u32,
+  size: u64,
+  // first ticket and ticket count of each order removed from the pool, in
+  // ascending order of first ticket
+  removed: Vec<(u32, u32)>,
+}
+
+impl<'a> TicketPool<'a> {
+  /// Load the pool of tickets in the given round.
+  pub fn load(
+    storage: &'a dyn Storage,
+    round_index: u32,
+  ) -> Result<Self, ContractError> {
+    let size = match ORDERS
+      .prefix(round_index)
+      .range(storage, None, None, Order::Descending)
+      .next()
+      .transpose()?
+    {
+      Some((_, last_order)) => last_order.last_ticket() as u64,
+      None => 0,
+    };
+    Ok(Self {
+      storage,
+      round_index,
+      size,
+      removed: vec![],
+    })
+  }
+
+  /// Remove all tickets held by the given wallet from the pool.
+  pub fn remove_wallet(
+    &mut self,
+    wallet: &Addr,
+  ) -> Result<(), ContractError> {
+    let player = match PLAYERS.may_load(self.storage, (self.round_index, wallet.clone()))? {
+      Some(player) => player,
+      None => return Ok(()),
+    };
+    for order_index in player.order_indices.iter() {
+      let order = ORDERS.load(self.storage, (self.round_index, *order_index))?;
+      if order.ticket_count == 0 {
+        continue;
+      }
+      let i = self
+        .removed
+        .partition_point(|(first_ticket, _)| *first_ticket < order.first_ticket);
+      if self.removed.get(i).map(|(first_ticket, _)| *first_ticket) != Some(order.first_ticket) {
+        self
+          .removed
+          .insert(i, (order.first_ticket, order.ticket_count));
+        self.size -= order.ticket_count as u64;
+      }
+    }
+    Ok(())
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.size == 0
+  }
+
+  /// Uniformly draw a ticket from the pool, returning the order containing it
+  /// along with the ticket's offset within the order. The ticket's number is
+  /// the entry's first_ticket plus the offset. Panics if the pool is empty.
+  pub fn draw(
+    &self,
+    rng: &mut Pcg64,
+  ) -> Result<(PoolEntry, u32), ContractError> {
+    // map the drawn rank among the remaining tickets to a ticket number by
+    // skipping over the ranges of removed orders that precede it
+    let mut ticket = rng.gen_range(self.size) as u32 + 1;
+    for (first_ticket, ticket_count) in self.removed.iter() {
+      if *first_ticket > ticket {
+        break;
+      }
+      ticket += ticket_count;
+    }
+    let (first_ticket, order_index) = TICKETS
+      .prefix(self.round_index)
+      .range(
+        self.storage,
+        None,
+        Some(Bound::inclusive(ticket)),
+        Order::Descending,
+      )
+      .next()
+      .transpose()?
+      .ok_or_else(|| StdError::not_found("TicketOrder"))?;
+    let order = ORDERS.load(self.storage, (self.round_index, order_index))?;
+    Ok((
+      PoolEntry {
+        order_index,
+        wallet: order.wallet,
+        first_ticket,
+      },
+      ticket - first_ticket,
+    ))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    models::round::WinnerSelectionMethod,
+    testing::{mock_config, mock_round},
+  };
+  use cosmwasm_std::{testing::MockStorage, Record};
+  use std::cell::Cell;
+
+  /// Storage that counts the entries read from it, standing in for the gas
+  /// charged per storage read.
+  struct CountingStorage {
+    inner: MockStorage,
+    reads: Cell<u64>,
+  }
+
+  impl Storage for CountingStorage {
+    fn get(
+      &self,
+      key: &[u8],
+    ) -> Option<Vec<u8>> {
+      self.reads.set(self.reads.get() + 1);
+      self.inner.get(key)
+    }
+
+    fn range<'a>(
+      &'a self,
+      start: Option<&[u8]>,
+      end: Option<&[u8]>,
+      order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'a> {
+      Box::new(
+        self
+          .inner
+          .range(start, end, order)
+          .inspect(move |_| self.reads.set(self.reads.get() + 1)),
+      )
+    }
+
+    fn set(
+      &mut self,
+      key: &[u8],
+      value: &[u8],
+    ) {
+      self.inner.set(key, value)
+    }
+
+    fn remove(
+      &mut self,
+      key: &[u8],
+    ) {
+      self.inner.remove(key)
+    }
+  }
+
+  /// Save a round with an order of the given number of tickets per wallet.
+  fn setup_round(
+    storage: &mut dyn Storage,
+    ticket_counts: &[u32],
+  ) {
+    let orders: Vec<(u32, u32)> = ticket_counts
+      .iter()
+      .enumerate()
+      .map(|(i, ticket_count)| (i as u32, *ticket_count))
+      .collect();
+    let config = mock_config(WinnerSelectionMethod::WinnerTakesAll, false);
+    mock_round(storage, &config, &orders);
+  }
+
+  #[test]
+  fn draws_tickets_within_the_drawn_order() {
+    let mut storage = MockStorage::new();
+    setup_round(&mut storage, &[3, 1, 5, 2]);
+    let pool = TicketPool::load(&storage, 0).unwrap();
+    let mut rng = Pcg64::from_seed([1; 32]);
+    let mut drawn = [false; 11];
+    for _ in 0..1000 {
+      let (entry, offset) = pool.draw(&mut rng).unwrap();
+      let order = ORDERS.load(&storage, (0, entry.order_index)).unwrap();
+      assert_eq!(entry.wallet, order.wallet);
+      assert_eq!(entry.first_ticket, order.first_ticket);
+      assert!(offset < order.ticket_count);
+      drawn[(entry.first_ticket + offset) as usize - 1] = true;
+    }
+    assert!(drawn.iter().all(|x| *x));
+  }
+
+  #[test]
+  fn removed_wallets_are_never_drawn() {
+    let mut storage = MockStorage::new();
+    setup_round(&mut storage, &[3, 1, 5, 2]);
+    let mut pool = TicketPool::load(&storage, 0).unwrap();
+    pool.remove_wallet(&Addr::unchecked("wallet2")).unwrap();
+    pool.remove_wallet(&Addr::unchecked("wallet0")).unwrap();
+    // removing a wallet twice leaves the pool unchanged
+    pool.remove_wallet(&Addr::unchecked("wallet0")).unwrap();
+    assert_eq!(pool.size, 3);
+    let mut rng = Pcg64::from_seed([2; 32]);
+    let mut drawn = [false; 11];
+    for _ in 0..1000 {
+      let (entry, offset) = pool.draw(&mut rng).unwrap();
+      assert!(entry.wallet == "wallet1" || entry.wallet == "wallet3");
+      drawn[(entry.first_ticket + offset) as usize - 1] = true;
+    }
+    // tickets 4, 10 and 11 belong to the remaining wallets
+    let expected: Vec<bool> = (1..=11).map(|t| t == 4 || t >= 10).collect();
+    assert_eq!(drawn.to_vec(), expected);
+    pool.remove_wallet(&Addr::unchecked("wallet1")).unwrap();
+    pool.remove_wallet(&Addr::unchecked("wallet3")).unwrap();
+    assert!(pool.is_empty());
+  }
+
+  #[test]
+  fn storage_reads_per_draw_are_independent_of_round_size() {
+    // 100,000 orders of 1 to 3 tickets each, about 200,000 tickets in all
+    let ticket_counts: Vec<u32> = (0..100_000).map(|i| 1 + i % 3).collect();
+    let mut storage = CountingStorage {
+      inner: MockStorage::new(),
+      reads: Cell::new(0),
+    };
+    setup_round(&mut storage, &ticket_counts);
+
+    storage.reads.set(0);
+    let mut pool = TicketPool::load(&storage, 0).unwrap();
+    assert_eq!(pool.size, 199_999);
+    let mut rng = Pcg64::from_seed([3; 32]);
+    let n_winners = 100;
+    for _ in 0..n_winners {
+      let (entry, _) = pool.draw(&mut rng).unwrap();
+      pool.remove_wallet(&entry.wallet).unwrap();
+    }
+    // loading the pool reads the last order, each draw reads an index entry
+    // and its order, and removing the winner reads its player and order.
+    assert_eq!(storage.reads.get(), 1 + n_winners * 4);
+  }
+}
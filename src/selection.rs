@@ -0,0 +1,178 @@
+//! Payout curves for each WinnerSelectionMethod, determining how many winners a
+//! round has and the pct of its winnings won by each position.
+use crate::{
+  error::ContractError,
+  models::round::{Tier, WinnerSelectionMethod},
+};
+use std::iter;
+
+// fixed-point scale of the weight of 1st place in a geometric decay curve
+const GEOMETRIC_SCALE: u128 = 1_000_000_000;
+
+/// Return the number of winners drawn from a round with the given number of
+/// ticket-holding wallets.
+pub fn winner_count(
+  method: &WinnerSelectionMethod,
+  n_wallets: u32,
+) -> u32 {
+  claim_percentages(method, n_wallets).len() as u32
+}
+
+/// Return the pct of the winnings won by each position, in order of position.
+/// Positions that can't be filled because there are too few wallets are
+/// dropped, and their pcts go to 1st place so that the pcts total 100. Pcts
+/// beyond a total of 100, as in a split configured before it was validated,
+/// are clamped so that later positions win only what remains.
+pub fn claim_percentages(
+  method: &WinnerSelectionMethod,
+  n_wallets: u32,
+) -> Vec<u8> {
+  if n_wallets == 0 {
+    return vec![];
+  }
+  let pcts = match method {
+    WinnerSelectionMethod::Percent { pct, max } => percent(*pct, *max, n_wallets),
+    WinnerSelectionMethod::Fixed(split) => fixed(split, n_wallets),
+    WinnerSelectionMethod::GeometricDecay { ratio, max } => {
+      geometric_decay(*ratio, *max, n_wallets)
+    },
+    WinnerSelectionMethod::Tiered(tiers) => tiered(tiers, n_wallets),
+    WinnerSelectionMethod::WinnerTakesAll => winner_takes_all(),
+  };
+  let mut remaining: u8 = 100;
+  let mut pcts: Vec<u8> = pcts
+    .into_iter()
+    .map(|pct| {
+      let pct = pct.min(remaining);
+      remaining -= pct;
+      pct
+    })
+    .filter(|pct| *pct > 0)
+    .collect();
+  if let Some(first) = pcts.first_mut() {
+    *first += remaining;
+  }
+  pcts
+}
+
+/// A number of winners derived as a pct of the wallets in the round, at least
+/// 1 and at most max, each winning an equal share.
+pub fn percent(
+  pct: u8,
+  max: Option<u32>,
+  n_wallets: u32,
+) -> Vec<u8> {
+  let mut n_winners = std::cmp::max(1, n_wallets * (pct as u32) / 100);
+  if let Some(n_max_winner) = max.filter(|n| *n > 0) {
+    n_winners = n_winners.min(n_max_winner);
+  }
+  // there can't be more winners than wallets in the round
+  n_winners = n_winners.min(n_wallets).min(100);
+  iter::repeat((100 / n_winners) as u8)
+    .take(n_winners as usize)
+    .collect()
+}
+
+/// A static split of pcts, like [70, 30], truncated to the number of wallets.
+pub fn fixed(
+  split: &[u8],
+  n_wallets: u32,
+) -> Vec<u8> {
+  let n_winners = std::cmp::min(n_wallets as usize, split.len());
+  split[..n_winners].to_vec()
+}
+
+/// Up to max winners, where each position wins ratio pct of the position
+/// before it. Positions whose share would round down to 0 are dropped.
+pub fn geometric_decay(
+  ratio: u8,
+  max: u32,
+  n_wallets: u32,
+) -> Vec<u8> {
+  let n_winners = max.min(n_wallets).min(100) as usize;
+  let mut weights: Vec<u128> = Vec::with_capacity(n_winners);
+  let mut weight = GEOMETRIC_SCALE;
+  while weights.len() < n_winners && weight > 0 {
+    weights.push(weight);
+    weight = weight * (ratio as u128) / 100;
+  }
+  let total_weight: u128 = weights.iter().sum();
+  weights
+    .iter()
+    .map(|weight| (weight * 100 / total_weight) as u8)
+    .filter(|pct| *pct > 0)
+    .collect()
+}
+
+/// Consecutive bands of winners, like 1 winner at 50%, 5 at 5% and 20 at 1%,
+/// truncated to the number of wallets.
+pub fn tiered(
+  tiers: &[Tier],
+  n_wallets: u32,
+) -> Vec<u8> {
+  tiers
+    .iter()
+    .flat_map(|tier| iter::repeat(tier.pct).take(tier.count as usize))
+    .take(n_wallets as usize)
+    .collect()
+}
+
+/// A single winner taking the entire pot.
+pub fn winner_takes_all() -> Vec<u8> {
+  vec![100]
+}
+
+/// Ensure the method's configured pcts total 100, with every position winning
+/// something.
+pub fn validate(method: &WinnerSelectionMethod) -> Result<(), ContractError> {
+  let reason = match method {
+    WinnerSelectionMethod::Percent { pct, max } => {
+      if *pct == 0 || *pct > 100 {
+        Some("winner pct must be between 1 and 100")
+      } else if matches!(max, Some(n) if *n > 100) {
+        Some("max winners cannot exceed 100")
+      } else {
+        None
+      }
+    },
+    WinnerSelectionMethod::Fixed(split) => {
+      if split.contains(&0) {
+        Some("split pcts must be greater than 0")
+      } else if split.iter().map(|pct| *pct as u32).sum::<u32>() != 100 {
+        Some("split pcts must total 100")
+      } else {
+        None
+      }
+    },
+    WinnerSelectionMethod::GeometricDecay { ratio, max } => {
+      if *ratio == 0 || *ratio > 100 {
+        Some("decay ratio must be between 1 and 100")
+      } else if *max == 0 || *max > 100 {
+        Some("max winners must be between 1 and 100")
+      } else {
+        None
+      }
+    },
+    WinnerSelectionMethod::Tiered(tiers) => {
+      if tiers.iter().any(|tier| tier.count == 0 || tier.pct == 0) {
+        Some("tier counts and pcts must be greater than 0")
+      } else if tiers
+        .iter()
+        .map(|tier| tier.count as u64 * tier.pct as u64)
+        .sum::<u64>()
+        != 100
+      {
+        Some("tier pcts must total 100")
+      } else {
+        None
+      }
+    },
+    WinnerSelectionMethod::WinnerTakesAll => None,
+  };
+  match reason {
+    Some(reason) => Err(ContractError::ValidationError {
+      reason: Some(reason.to_owned()),
+    }),
+    None => Ok(()),
+  }
+}
@@ -1,5 +1,7 @@
+use crate::models::jackpot::Jackpot;
 use crate::models::lottery::Lottery;
 use crate::models::player::Player;
+use crate::models::role::Role;
 use crate::models::round::Round;
 use crate::models::royalties::Claim;
 use crate::models::ticket_order::TicketOrder;
@@ -11,13 +13,19 @@ use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Storage};
 use cw_storage_plus::{Item, Map};
 
 pub const LOTTERY: Item<Lottery> = Item::new("lottery");
+pub const JACKPOT: Item<Jackpot> = Item::new("jackpot");
+pub const ROLES: Map<Addr, Vec<Role>> = Map::new("roles");
 pub const ROUNDS: Map<u32, Round> = Map::new("rounds");
 pub const INCENTIVES: Map<u32, Vec<Incentive>> = Map::new("incentives");
 pub const PLAYERS: Map<(u32, Addr), Player> = Map::new("player");
-pub const WINNERS: Map<(u32, Addr), Winner> = Map::new("winners");
+pub const WINNERS: Map<(u32, u32), Winner> = Map::new("winners");
 pub const ORDERS: Map<(u32, u32), TicketOrder> = Map::new("orders");
+// index of each ticket order by its first ticket, for drawing tickets
+pub const TICKETS: Map<(u32, u32), u32> = Map::new("tickets");
 pub const CLAIMS: Map<Addr, Claim> = Map::new("claims");
 pub const SEED: Item<String> = Item::new("seed");
+pub const ROUND_SEEDS: Map<u32, String> = Map::new("round_seeds");
+pub const COMMITMENTS: Map<u32, String> = Map::new("commitments");
 
 /// Initialize contract state data.
 pub fn initialize(
@@ -26,8 +34,14 @@ pub fn initialize(
   info: &MessageInfo,
   msg: &InstantiateMsg,
 ) -> Result<Lottery, ContractError> {
-  let lottery = Lottery::instantiate(info, msg)?;
-  let round = Round::new(env.block.time, lottery.is_active(), 0);
+  let lottery = Lottery::instantiate(env, info, msg)?;
+  let round = Round::new(
+    env.block.time,
+    lottery.is_active(),
+    0,
+    0,
+    &lottery.rounds.configs[0],
+  );
 
   LOTTERY.save(deps.storage, &lottery)?;
   ROUNDS.save(deps.storage, 0, &round)?;
@@ -44,6 +58,23 @@ pub fn load_round(
   Ok(ROUNDS.load(storage, round_index.unwrap_or(lottery.rounds.index))?)
 }
 
+/// Abort unless the sender is the lottery owner or has been granted the role.
+pub fn ensure_role(
+  storage: &dyn Storage,
+  lottery: &Lottery,
+  sender: &Addr,
+  role: Role,
+) -> Result<(), ContractError> {
+  if lottery.is_owner(sender) {
+    return Ok(());
+  }
+  let roles = ROLES.may_load(storage, sender.clone())?.unwrap_or_default();
+  if !roles.contains(&role) {
+    return Err(ContractError::NotAuthorized {});
+  }
+  Ok(())
+}
+
 pub fn load_player(
   storage: &mut dyn Storage,
   round_index: u32,
@@ -68,6 +99,9 @@ pub fn remove_player_from_round(
   PLAYERS.remove(storage, (round_index, player.wallet.clone()));
   // remove each order
   for order_index in player.order_indices.iter() {
+    if let Some(order) = ORDERS.may_load(storage, (round_index, *order_index))? {
+      TICKETS.remove(storage, (round_index, order.first_ticket));
+    }
     ORDERS.remove(storage, (round_index, *order_index));
   }
   Ok(())
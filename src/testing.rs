@@ -0,0 +1,115 @@
+//! Fixtures shared by unit tests.
+use cosmwasm_std::{
+  testing::{mock_env, mock_info},
+  Addr, DepsMut, Storage, Timestamp, Uint128,
+};
+
+use crate::{
+  contract,
+  models::{
+    player::Player,
+    round::{Config, Round, Targets, Token, WinnerSelection, WinnerSelectionMethod},
+    ticket_order::TicketOrder,
+  },
+  msg::{InitialRounds, InstantiateMsg},
+  state::{ORDERS, PLAYERS, TICKETS},
+};
+
+/// Return a config for rounds of native tokens with no end conditions.
+pub fn mock_config(
+  method: WinnerSelectionMethod,
+  with_replacement: bool,
+) -> Config {
+  Config {
+    name: None,
+    targets: Targets {
+      funding_level: None,
+      duration_minutes: None,
+      block_height: None,
+      ticket_count: None,
+      wallet_count: None,
+      extension: None,
+      mode: None,
+    },
+    selection: WinnerSelection {
+      method,
+      with_replacement,
+    },
+    token: Token::Native {
+      denom: "ujuno".to_owned(),
+    },
+    ticket_price: Uint128::from(1u128),
+    max_tickets_per_wallet: None,
+    royalties: vec![],
+    min_wallets: None,
+    min_pot: None,
+    threshold_policy: None,
+    randomness: None,
+    jackpot: None,
+  }
+}
+
+/// Instantiate an active lottery of the given number of rounds, each won by a
+/// single winner.
+pub fn mock_instantiate(
+  deps: DepsMut,
+  count: u32,
+) {
+  let msg = InstantiateMsg {
+    name: None,
+    rounds: InitialRounds {
+      configs: vec![mock_config(WinnerSelectionMethod::WinnerTakesAll, false)],
+      count,
+    },
+    tournament: None,
+    activate: Some(true),
+    start_at: None,
+  };
+  contract::instantiate(deps, mock_env(), mock_info("owner", &[]), msg).unwrap();
+}
+
+/// Save the orders of round 0, given as (wallet index, ticket count) pairs in
+/// the order they're placed, numbering their tickets the way buy_tickets does.
+/// Each wallet index stands for the wallet named "wallet{index}". Returns the
+/// round with its counts, which is left unsaved.
+pub fn mock_round(
+  storage: &mut dyn Storage,
+  config: &Config,
+  orders: &[(u32, u32)],
+) -> Round {
+  let mut round = Round::new(Timestamp::from_seconds(0), true, 0, 0, config);
+  let mut first_ticket = 1;
+  for (order_index, (wallet_index, ticket_count)) in orders.iter().enumerate() {
+    let order_index = order_index as u32;
+    let wallet = Addr::unchecked(format!("wallet{}", wallet_index));
+    let order = TicketOrder {
+      wallet: wallet.clone(),
+      first_ticket,
+      ticket_count: *ticket_count,
+      message: None,
+      is_public: false,
+    };
+    let mut player = match PLAYERS.may_load(storage, (0, wallet.clone())).unwrap() {
+      Some(player) => player,
+      None => {
+        round.counts.wallets += 1;
+        Player {
+          wallet: wallet.clone(),
+          ticket_count: 0,
+          order_indices: vec![],
+        }
+      },
+    };
+    player.ticket_count += ticket_count;
+    player.order_indices.push(order_index);
+    PLAYERS.save(storage, (0, wallet), &player).unwrap();
+    ORDERS.save(storage, (0, order_index), &order).unwrap();
+    TICKETS
+      .save(storage, (0, first_ticket), &order_index)
+      .unwrap();
+    first_ticket = order.next_ticket();
+    round.counts.orders += 1;
+    round.counts.tickets += ticket_count;
+  }
+  round
+}
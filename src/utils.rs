@@ -1,7 +1,10 @@
 use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, SubMsg, Uint128, WasmMsg};
 use cw20::{Cw20ExecuteMsg, Cw20QueryMsg};
 
-use crate::error::ContractError;
+use crate::{
+  error::ContractError,
+  models::round::{RoyaltyRecipient, Token},
+};
 
 pub fn build_cw20_transfer_msg(
   from_address: &Addr,
@@ -22,6 +25,23 @@ pub fn build_cw20_transfer_msg(
   }))
 }
 
+/// Build a submsg that transfers CW20 tokens held by this contract to the
+/// given address.
+pub fn build_cw20_send_msg(
+  to_address: &Addr,
+  cw20_token_address: &Addr,
+  amount: Uint128,
+) -> Result<SubMsg, ContractError> {
+  Ok(SubMsg::new(WasmMsg::Execute {
+    contract_addr: cw20_token_address.clone().into(),
+    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+      recipient: to_address.clone().into(),
+      amount,
+    })?,
+    funds: vec![],
+  }))
+}
+
 /// Return a Response that performs a bank transfer of native funds to the
 /// contract. Validates the payment amount sent in the tx.
 pub fn build_native_send_msg(
@@ -36,6 +56,36 @@ pub fn build_native_send_msg(
   }))
 }
 
+/// Build a submsg that transfers the given amount of native or CW20 tokens
+/// held by this contract to the given address.
+pub fn build_send_submsg(
+  token: &Token,
+  to_address: &Addr,
+  amount: Uint128,
+) -> Result<SubMsg, ContractError> {
+  Ok(match token {
+    Token::Native { denom } => SubMsg::new(build_native_send_msg(to_address, denom, amount)?),
+    Token::Cw20 { address } => build_cw20_send_msg(to_address, address, amount)?,
+  })
+}
+
+/// Build submsgs that pay each autosend royalty recipient its pct of the
+/// round's pot out of this contract's balance.
+pub fn build_royalty_transfer_submsgs(
+  royalties: &[RoyaltyRecipient],
+  token: &Token,
+  pot_size: Uint128,
+) -> Result<Vec<SubMsg>, ContractError> {
+  let mut submsgs: Vec<SubMsg> = Vec::with_capacity(royalties.len());
+  for royalty in royalties.iter() {
+    let royalty_amount = apply_pct(pot_size, royalty.pct);
+    if !royalty_amount.is_zero() {
+      submsgs.push(build_send_submsg(token, &royalty.address, royalty_amount)?);
+    }
+  }
+  Ok(submsgs)
+}
+
 // Check for the payment amount required by querying the CW20 token contract.
 pub fn verify_cw20_funds(
   deps: &DepsMut,
@@ -57,7 +107,7 @@ pub fn verify_cw20_funds(
 
 // Check for the exact payment amount required in the tx's funds.
 pub fn verify_native_funds(
-  funds: &Vec<Coin>,
+  funds: &[Coin],
   payment_amount: Uint128,
   denom: &String,
 ) -> Result<(), ContractError> {
@@ -0,0 +1,133 @@
+mod common;
+
+use base64ct::{Base64, Encoding};
+use common::*;
+use cosmwasm_std::Addr;
+use cw_gelotto_lottery::{
+  models::round::{RandomnessSource, RoundStatus},
+  msg::ExecuteMsg,
+};
+use cw_multi_test::{App, AppResponse};
+use sha2::{Digest, Sha256};
+
+const SECRET: &str = "correct horse battery staple";
+const REVEAL_TIMEOUT_MINUTES: u32 = 30;
+
+/// Instantiate a lottery whose rounds are drawn with a secret revealed by the
+/// owner after they close.
+fn setup(app: &mut App) -> Addr {
+  let mut config = config(native_token());
+  config.randomness = Some(RandomnessSource::CommitReveal {
+    reveal_timeout_minutes: REVEAL_TIMEOUT_MINUTES,
+  });
+  instantiate_lottery(app, vec![config], 2)
+}
+
+fn commit(
+  app: &mut App,
+  lottery: &Addr,
+) {
+  let commitment = Base64::encode_string(&Sha256::digest(SECRET.as_bytes()));
+  execute(
+    app,
+    OWNER,
+    lottery,
+    &ExecuteMsg::CommitSecret {
+      round: 0,
+      commitment,
+    },
+  )
+  .unwrap();
+}
+
+/// Commit to the secret and have alice and bob close round 0.
+fn close_round(app: &mut App) -> Addr {
+  let lottery = setup(app);
+  commit(app, &lottery);
+  buy_tickets(app, &lottery, "alice", 1).unwrap();
+  buy_tickets(app, &lottery, "bob", 2).unwrap();
+  assert_eq!(query_round(app, &lottery, 0).status, RoundStatus::Closed);
+  lottery
+}
+
+fn reveal(
+  app: &mut App,
+  lottery: &Addr,
+  secret: &str,
+) -> anyhow::Result<AppResponse> {
+  execute(
+    app,
+    OWNER,
+    lottery,
+    &ExecuteMsg::RevealSecret {
+      round: 0,
+      secret: secret.to_owned(),
+    },
+  )
+}
+
+#[test]
+fn tickets_can_only_be_bought_once_the_secret_is_committed() {
+  let mut app = mock_app(&["alice"]);
+  let lottery = setup(&mut app);
+
+  assert_eq!(
+    error_name(buy_tickets(&mut app, &lottery, "alice", 1).unwrap_err()),
+    "MissingCommitment"
+  );
+  commit(&mut app, &lottery);
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+}
+
+#[test]
+fn revealing_the_secret_draws_the_round() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = close_round(&mut app);
+
+  assert_eq!(
+    error_name(reveal(&mut app, &lottery, "wrong secret").unwrap_err()),
+    "InvalidSecret"
+  );
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Closed);
+
+  reveal(&mut app, &lottery, SECRET).unwrap();
+  let round = query_round(&app, &lottery, 0);
+  assert_eq!(round.status, RoundStatus::Complete);
+  assert_eq!(round.winners.unwrap().len(), 1);
+}
+
+#[test]
+fn the_secret_is_rejected_after_the_reveal_timeout() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = close_round(&mut app);
+
+  advance_minutes(&mut app, REVEAL_TIMEOUT_MINUTES as u64 + 1);
+  assert_eq!(
+    error_name(reveal(&mut app, &lottery, SECRET).unwrap_err()),
+    "NotActive"
+  );
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Closed);
+}
+
+#[test]
+fn players_are_refunded_when_the_secret_is_never_revealed() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = close_round(&mut app);
+
+  // the round can't be terminated while the secret may still be revealed
+  advance_minutes(&mut app, REVEAL_TIMEOUT_MINUTES as u64);
+  assert_eq!(
+    error_name(execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap_err()),
+    "RoundNotReady"
+  );
+
+  advance_minutes(&mut app, 1);
+  execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap();
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Canceled);
+
+  for wallet in ["alice", "bob"] {
+    execute(&mut app, wallet, &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+    assert_eq!(native_balance(&app, wallet), INITIAL_BALANCE);
+  }
+  assert_eq!(native_balance(&app, lottery.as_str()), 0);
+}
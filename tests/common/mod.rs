@@ -0,0 +1,235 @@
+//! Helpers shared by the integration tests, which run the lottery contract in
+//! a cw-multi-test App.
+#![allow(dead_code)]
+
+pub mod token;
+
+use cosmwasm_std::{coins, Addr, Empty, Timestamp, Uint128};
+use cw_gelotto_lottery::{
+  contract,
+  models::{
+    incentive::TokenAmount,
+    jackpot::JackpotWin,
+    lottery::Lottery,
+    round::{Config, Counts, RoundStatus, Targets, Token, WinnerSelection, WinnerSelectionMethod},
+    winner::Winner,
+  },
+  msg::{ExecuteMsg, InitialRounds, InstantiateMsg, QueryMsg},
+};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use serde::Deserialize;
+
+pub const DENOM: &str = "ujuno";
+pub const OWNER: &str = "owner";
+pub const TICKET_PRICE: u128 = 100;
+pub const INITIAL_BALANCE: u128 = 1_000_000;
+
+pub fn lottery_contract() -> Box<dyn Contract<Empty>> {
+  Box::new(
+    ContractWrapper::new(contract::execute, contract::instantiate, contract::query)
+      .with_migrate(contract::migrate),
+  )
+}
+
+/// Return an App in which each of the given wallets holds a balance of the
+/// native ticket token.
+pub fn mock_app(wallets: &[&str]) -> App {
+  App::new(|router, _, storage| {
+    for wallet in wallets.iter() {
+      router
+        .bank
+        .init_balance(
+          storage,
+          &Addr::unchecked(*wallet),
+          coins(INITIAL_BALANCE, DENOM),
+        )
+        .unwrap();
+    }
+  })
+}
+
+/// Return a config for rounds that end once two wallets hold tickets, won
+/// entirely by a single winner.
+pub fn config(token: Token) -> Config {
+  Config {
+    name: None,
+    targets: Targets {
+      funding_level: None,
+      duration_minutes: None,
+      block_height: None,
+      ticket_count: None,
+      wallet_count: Some(2),
+      extension: None,
+      mode: None,
+    },
+    selection: WinnerSelection {
+      method: WinnerSelectionMethod::WinnerTakesAll,
+      with_replacement: false,
+    },
+    token,
+    ticket_price: Uint128::from(TICKET_PRICE),
+    max_tickets_per_wallet: None,
+    royalties: vec![],
+    min_wallets: None,
+    min_pot: None,
+    threshold_policy: None,
+    randomness: None,
+    jackpot: None,
+  }
+}
+
+pub fn native_token() -> Token {
+  Token::Native {
+    denom: DENOM.to_owned(),
+  }
+}
+
+/// Return the message instantiating an active lottery with the given configs
+/// and number of rounds.
+pub fn instantiate_msg(
+  configs: Vec<Config>,
+  count: u32,
+) -> InstantiateMsg {
+  InstantiateMsg {
+    name: None,
+    rounds: InitialRounds { configs, count },
+    tournament: None,
+    activate: Some(true),
+    start_at: None,
+  }
+}
+
+pub fn instantiate(
+  app: &mut App,
+  msg: &InstantiateMsg,
+) -> Addr {
+  let code_id = app.store_code(lottery_contract());
+  app
+    .instantiate_contract(
+      code_id,
+      Addr::unchecked(OWNER),
+      msg,
+      &[],
+      "lottery",
+      Some(OWNER.to_owned()),
+    )
+    .unwrap()
+}
+
+/// Instantiate an active lottery with the given configs and number of rounds.
+pub fn instantiate_lottery(
+  app: &mut App,
+  configs: Vec<Config>,
+  count: u32,
+) -> Addr {
+  instantiate(app, &instantiate_msg(configs, count))
+}
+
+pub fn execute(
+  app: &mut App,
+  sender: &str,
+  lottery: &Addr,
+  msg: &ExecuteMsg,
+) -> anyhow::Result<AppResponse> {
+  app.execute_contract(Addr::unchecked(sender), lottery.clone(), msg, &[])
+}
+
+/// Buy tickets in the current round with the native ticket token.
+pub fn buy_tickets(
+  app: &mut App,
+  lottery: &Addr,
+  wallet: &str,
+  count: u32,
+) -> anyhow::Result<AppResponse> {
+  app.execute_contract(
+    Addr::unchecked(wallet),
+    lottery.clone(),
+    &ExecuteMsg::BuyTickets {
+      count,
+      message: None,
+      is_public: None,
+    },
+    &coins(TICKET_PRICE * count as u128, DENOM),
+  )
+}
+
+/// The parts of a GetRound response checked by the tests.
+#[derive(Deserialize, Debug)]
+pub struct RoundView {
+  pub config: Config,
+  pub config_index: u32,
+  pub status: RoundStatus,
+  pub counts: Counts,
+  pub carryover: Uint128,
+  pub winners: Option<Vec<Winner>>,
+  pub started_at: Option<Timestamp>,
+  pub closed_at: Option<Timestamp>,
+  pub jackpot_win: Option<JackpotWin>,
+  pub jackpot: Vec<TokenAmount>,
+}
+
+pub fn query_round(
+  app: &App,
+  lottery: &Addr,
+  index: u32,
+) -> RoundView {
+  app
+    .wrap()
+    .query_wasm_smart(
+      lottery,
+      &QueryMsg::GetRound {
+        index,
+        players: None,
+        winners: Some(true),
+        orders: None,
+      },
+    )
+    .unwrap()
+}
+
+/// The parts of a GetLottery response checked by the tests.
+#[derive(Deserialize, Debug)]
+struct LotteryView {
+  lottery: Lottery,
+}
+
+pub fn query_lottery(
+  app: &App,
+  lottery: &Addr,
+) -> Lottery {
+  let response: LotteryView = app
+    .wrap()
+    .query_wasm_smart(lottery, &QueryMsg::GetLottery {})
+    .unwrap();
+  response.lottery
+}
+
+pub fn native_balance(
+  app: &App,
+  wallet: &str,
+) -> u128 {
+  app
+    .wrap()
+    .query_balance(wallet, DENOM)
+    .unwrap()
+    .amount
+    .u128()
+}
+
+/// Advance the block time by the given number of minutes, and the block
+/// height by one.
+pub fn advance_minutes(
+  app: &mut App,
+  minutes: u64,
+) {
+  app.update_block(|block| {
+    block.time = block.time.plus_seconds(minutes * 60);
+    block.height += 1;
+  });
+}
+
+/// Return the message of the root cause of a failed execution, which is the
+/// name of the ContractError variant for errors raised by the lottery.
+pub fn error_name(err: anyhow::Error) -> String {
+  err.root_cause().to_string()
+}
@@ -0,0 +1,132 @@
+//! A minimal cw20 token for running cw20 rounds in tests. Allowances aren't
+//! tracked, so TransferFrom only checks the owner's balance.
+use cosmwasm_std::{
+  to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+  Uint128,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+use super::{INITIAL_BALANCE, OWNER};
+
+const BALANCES: Map<Addr, Uint128> = Map::new("balances");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InstantiateMsg {
+  pub balances: Vec<(Addr, Uint128)>,
+}
+
+fn instantiate(
+  deps: DepsMut,
+  _env: Env,
+  _info: MessageInfo,
+  msg: InstantiateMsg,
+) -> StdResult<Response> {
+  for (wallet, amount) in msg.balances.iter() {
+    BALANCES.save(deps.storage, wallet.clone(), amount)?;
+  }
+  Ok(Response::new())
+}
+
+fn transfer(
+  deps: DepsMut,
+  from: Addr,
+  to: Addr,
+  amount: Uint128,
+) -> StdResult<Response> {
+  BALANCES.update(deps.storage, from, |balance| {
+    balance
+      .unwrap_or_default()
+      .checked_sub(amount)
+      .map_err(StdError::overflow)
+  })?;
+  BALANCES.update(deps.storage, to, |balance| -> StdResult<_> {
+    Ok(balance.unwrap_or_default() + amount)
+  })?;
+  Ok(Response::new())
+}
+
+fn execute(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  msg: Cw20ExecuteMsg,
+) -> StdResult<Response> {
+  match msg {
+    Cw20ExecuteMsg::Transfer { recipient, amount } => {
+      transfer(deps, info.sender, Addr::unchecked(recipient), amount)
+    },
+    Cw20ExecuteMsg::TransferFrom {
+      owner,
+      recipient,
+      amount,
+    } => transfer(
+      deps,
+      Addr::unchecked(owner),
+      Addr::unchecked(recipient),
+      amount,
+    ),
+    _ => Err(StdError::generic_err("unsupported")),
+  }
+}
+
+fn query(
+  deps: Deps,
+  _env: Env,
+  msg: Cw20QueryMsg,
+) -> StdResult<Binary> {
+  match msg {
+    Cw20QueryMsg::Balance { address } => to_binary(&BalanceResponse {
+      balance: BALANCES
+        .may_load(deps.storage, Addr::unchecked(address))?
+        .unwrap_or_default(),
+    }),
+    _ => Err(StdError::generic_err("unsupported")),
+  }
+}
+
+fn contract() -> Box<dyn Contract<Empty>> {
+  Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+/// Instantiate a token in which each of the given wallets holds a balance.
+pub fn instantiate_token(
+  app: &mut App,
+  wallets: &[&str],
+) -> Addr {
+  let code_id = app.store_code(contract());
+  app
+    .instantiate_contract(
+      code_id,
+      Addr::unchecked(OWNER),
+      &InstantiateMsg {
+        balances: wallets
+          .iter()
+          .map(|wallet| (Addr::unchecked(*wallet), Uint128::from(INITIAL_BALANCE)))
+          .collect(),
+      },
+      &[],
+      "token",
+      None,
+    )
+    .unwrap()
+}
+
+pub fn balance(
+  app: &App,
+  token: &Addr,
+  wallet: &str,
+) -> u128 {
+  let response: BalanceResponse = app
+    .wrap()
+    .query_wasm_smart(
+      token,
+      &Cw20QueryMsg::Balance {
+        address: wallet.to_owned(),
+      },
+    )
+    .unwrap();
+  response.balance.u128()
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use cw_gelotto_lottery::{
+  models::round::{Config, RoundStatus},
+  msg::ExecuteMsg,
+};
+use cw_multi_test::App;
+
+fn priced(price: u128) -> Config {
+  let mut config = config(native_token());
+  config.ticket_price = Uint128::from(price);
+  config
+}
+
+/// Buy tickets for alice and bob, ending the current round.
+fn play_round(
+  app: &mut App,
+  lottery: &Addr,
+) {
+  buy_tickets(app, lottery, "alice", 1).unwrap();
+  buy_tickets(app, lottery, "bob", 1).unwrap();
+}
+
+#[test]
+fn appended_configs_take_their_turn_after_the_existing_ones() {
+  let mut app = mock_app(&["alice", "bob", "carol"]);
+  let mut msg = instantiate_msg(vec![priced(TICKET_PRICE), priced(TICKET_PRICE)], 4);
+  msg.tournament = Some(true);
+  let lottery = instantiate(&mut app, &msg);
+
+  // round 1 is the 2nd round of the tournament, and round 2 starts it over
+  play_round(&mut app, &lottery);
+  play_round(&mut app, &lottery);
+  assert_eq!(query_round(&app, &lottery, 2).config_index, 0);
+
+  execute(
+    &mut app,
+    OWNER,
+    &lottery,
+    &ExecuteMsg::AppendRounds {
+      configs: vec![priced(3 * TICKET_PRICE)],
+      count: Some(5),
+    },
+  )
+  .unwrap();
+
+  // the current round keeps its config, so carol can still join it
+  assert_eq!(query_round(&app, &lottery, 2).config_index, 0);
+  buy_tickets(&mut app, &lottery, "carol", 1).unwrap();
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+
+  // the next rounds cycle on from it, reaching the appended config last
+  let round = query_round(&app, &lottery, 3);
+  assert_eq!(round.config_index, 1);
+  assert_eq!(round.config.ticket_price.u128(), TICKET_PRICE);
+  assert_eq!(
+    error_name(buy_tickets(&mut app, &lottery, "bob", 1).unwrap_err()),
+    "Forbidden"
+  );
+  buy_tickets(&mut app, &lottery, "carol", 1).unwrap();
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+
+  let round = query_round(&app, &lottery, 4);
+  assert_eq!(round.config_index, 2);
+  assert_eq!(round.config.ticket_price.u128(), 3 * TICKET_PRICE);
+}
+
+#[test]
+fn updating_a_config_refreshes_a_pending_round_without_orders() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let mut msg = instantiate_msg(vec![priced(TICKET_PRICE)], 2);
+  msg.activate = Some(false);
+  let lottery = instantiate(&mut app, &msg);
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Pending);
+
+  let update = ExecuteMsg::UpdateConfig {
+    index: 0,
+    config: priced(2 * TICKET_PRICE),
+  };
+  execute(&mut app, OWNER, &lottery, &update).unwrap();
+  let price = |app: &App| query_round(app, &lottery, 0).config.ticket_price.u128();
+  assert_eq!(price(&app), 2 * TICKET_PRICE);
+
+  // once the round is active, its economics are fixed
+  execute(&mut app, OWNER, &lottery, &ExecuteMsg::Activate {}).unwrap();
+  let update = ExecuteMsg::UpdateConfig {
+    index: 0,
+    config: priced(3 * TICKET_PRICE),
+  };
+  execute(&mut app, OWNER, &lottery, &update).unwrap();
+  assert_eq!(price(&app), 2 * TICKET_PRICE);
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Active);
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Addr;
+use cw_gelotto_lottery::{
+  models::round::{RandomnessSource, RoundStatus},
+  msg::ExecuteMsg,
+};
+use cw_multi_test::{App, AppResponse};
+
+const DELAY: u64 = 5;
+const WINDOW: u64 = 10;
+
+/// Instantiate a lottery drawn with a future block, and have alice and bob
+/// close its first round, returning the lottery and the round's draw height.
+fn close_round(app: &mut App) -> (Addr, u64) {
+  let mut config = config(native_token());
+  config.randomness = Some(RandomnessSource::FutureBlock {
+    delay: DELAY,
+    window: WINDOW,
+  });
+  let lottery = instantiate_lottery(app, vec![config], 2);
+  buy_tickets(app, &lottery, "alice", 1).unwrap();
+  buy_tickets(app, &lottery, "bob", 2).unwrap();
+  assert_eq!(query_round(app, &lottery, 0).status, RoundStatus::Closed);
+  (lottery, app.block_info().height + DELAY)
+}
+
+fn advance_to_height(
+  app: &mut App,
+  height: u64,
+) {
+  app.update_block(|block| {
+    block.time = block.time.plus_seconds(5 * (height - block.height));
+    block.height = height;
+  });
+}
+
+fn draw(
+  app: &mut App,
+  lottery: &Addr,
+) -> anyhow::Result<AppResponse> {
+  execute(app, "anyone", lottery, &ExecuteMsg::Draw { round: 0 })
+}
+
+#[test]
+fn the_round_is_drawn_once_its_draw_height_is_reached() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let (lottery, draw_height) = close_round(&mut app);
+
+  // no tickets can be bought while the round waits on its draw height
+  assert_eq!(
+    error_name(buy_tickets(&mut app, &lottery, "alice", 1).unwrap_err()),
+    "RoundClosed"
+  );
+
+  advance_to_height(&mut app, draw_height - 1);
+  assert_eq!(
+    error_name(draw(&mut app, &lottery).unwrap_err()),
+    "RoundNotReady"
+  );
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Closed);
+
+  advance_to_height(&mut app, draw_height);
+  draw(&mut app, &lottery).unwrap();
+  let round = query_round(&app, &lottery, 0);
+  assert_eq!(round.status, RoundStatus::Complete);
+  assert_eq!(round.winners.unwrap().len(), 1);
+  assert_eq!(query_round(&app, &lottery, 1).status, RoundStatus::Active);
+}
+
+#[test]
+fn a_draw_past_the_window_is_rejected_and_players_are_refunded() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let (lottery, draw_height) = close_round(&mut app);
+
+  // the round can be drawn up to the last block of its window
+  advance_to_height(&mut app, draw_height + WINDOW);
+  assert_eq!(
+    error_name(execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap_err()),
+    "RoundNotReady"
+  );
+
+  advance_to_height(&mut app, draw_height + WINDOW + 1);
+  assert_eq!(
+    error_name(draw(&mut app, &lottery).unwrap_err()),
+    "NotActive"
+  );
+
+  execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap();
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Canceled);
+  for wallet in ["alice", "bob"] {
+    execute(&mut app, wallet, &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+    assert_eq!(native_balance(&app, wallet), INITIAL_BALANCE);
+  }
+  assert_eq!(native_balance(&app, lottery.as_str()), 0);
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Addr;
+use cw_gelotto_lottery::{
+  models::{
+    jackpot::JackpotWin,
+    round::{Config, JackpotConfig, RoundStatus},
+  },
+  msg::ExecuteMsg,
+};
+use cw_multi_test::App;
+
+const JACKPOT_PCT: u8 = 10;
+// pot of each round played by play_round
+const POT: u128 = 5 * TICKET_PRICE;
+const CONTRIBUTION: u128 = POT * JACKPOT_PCT as u128 / 100;
+
+fn with_jackpot(odds: u32) -> Config {
+  let mut config = config(native_token());
+  config.jackpot = Some(JackpotConfig {
+    pct: JACKPOT_PCT,
+    odds,
+  });
+  config
+}
+
+/// Buy tickets for alice and bob, ending the current round.
+fn play_round(
+  app: &mut App,
+  lottery: &Addr,
+) {
+  buy_tickets(app, lottery, "alice", 4).unwrap();
+  buy_tickets(app, lottery, "bob", 1).unwrap();
+}
+
+fn reserve(
+  app: &App,
+  lottery: &Addr,
+  round: u32,
+) -> u128 {
+  let round = query_round(app, lottery, round);
+  round.jackpot.iter().map(|x| x.amount.u128()).sum()
+}
+
+fn jackpot_win(
+  app: &App,
+  lottery: &Addr,
+  round: u32,
+) -> Option<JackpotWin> {
+  query_round(app, lottery, round).jackpot_win
+}
+
+/// Have alice and bob claim their rewards, if any, returning the amount each
+/// of them received.
+fn claim_rewards(
+  app: &mut App,
+  lottery: &Addr,
+) -> (u128, u128) {
+  let mut received = vec![];
+  for wallet in ["alice", "bob"] {
+    let balance = native_balance(app, wallet);
+    execute(app, wallet, lottery, &ExecuteMsg::ClaimRewards {}).ok();
+    received.push(native_balance(app, wallet) - balance);
+  }
+  (received[0], received[1])
+}
+
+#[test]
+fn the_jackpot_is_won_against_its_odds() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = instantiate_lottery(&mut app, vec![with_jackpot(1)], 3);
+  play_round(&mut app, &lottery);
+
+  // with odds of 1 in 1, the round's contribution is won right away
+  let win = jackpot_win(&app, &lottery, 0).unwrap();
+  assert!(!win.forced);
+  assert_eq!(win.balances[0].amount.u128(), CONTRIBUTION);
+  assert_eq!(reserve(&app, &lottery, 0), 0);
+
+  let (alice, bob) = claim_rewards(&mut app, &lottery);
+  assert_eq!(alice + bob, POT);
+  assert_eq!(native_balance(&app, lottery.as_str()), 0);
+}
+
+#[test]
+fn the_jackpot_rolls_forward_until_it_is_won() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = instantiate_lottery(&mut app, vec![with_jackpot(u32::MAX)], 3);
+
+  play_round(&mut app, &lottery);
+  assert_eq!(jackpot_win(&app, &lottery, 0), None);
+  assert_eq!(reserve(&app, &lottery, 1), CONTRIBUTION);
+
+  play_round(&mut app, &lottery);
+  assert_eq!(jackpot_win(&app, &lottery, 1), None);
+  assert_eq!(reserve(&app, &lottery, 2), 2 * CONTRIBUTION);
+
+  // only the winnings of each round can be claimed so far
+  let (alice, bob) = claim_rewards(&mut app, &lottery);
+  assert_eq!(alice + bob, 2 * (POT - CONTRIBUTION));
+  assert_eq!(native_balance(&app, lottery.as_str()), 2 * CONTRIBUTION);
+}
+
+#[test]
+fn the_final_round_is_forced_to_award_the_jackpot() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = instantiate_lottery(&mut app, vec![with_jackpot(u32::MAX)], 2);
+
+  play_round(&mut app, &lottery);
+  assert_eq!(jackpot_win(&app, &lottery, 0), None);
+  play_round(&mut app, &lottery);
+
+  let win = jackpot_win(&app, &lottery, 1).unwrap();
+  assert!(win.forced);
+  assert_eq!(win.balances[0].amount.u128(), 2 * CONTRIBUTION);
+  assert_eq!(reserve(&app, &lottery, 1), 0);
+
+  let (alice, bob) = claim_rewards(&mut app, &lottery);
+  assert_eq!(alice + bob, 2 * POT);
+  assert_eq!(native_balance(&app, lottery.as_str()), 0);
+}
+
+#[test]
+fn canceling_the_lottery_returns_the_jackpot_to_the_last_winners() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = instantiate_lottery(&mut app, vec![with_jackpot(u32::MAX)], 3);
+
+  play_round(&mut app, &lottery);
+  let winner = query_round(&app, &lottery, 0).winners.unwrap()[0]
+    .wallet
+    .to_string();
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+
+  execute(&mut app, OWNER, &lottery, &ExecuteMsg::CancelLottery {}).unwrap();
+  assert_eq!(query_round(&app, &lottery, 1).status, RoundStatus::Canceled);
+  assert_eq!(reserve(&app, &lottery, 1), 0);
+
+  // the reserve goes to round 0's winner rather than the owner
+  assert_eq!(
+    error_name(execute(&mut app, OWNER, &lottery, &ExecuteMsg::ClaimRewards {}).unwrap_err()),
+    "NothingToClaim"
+  );
+  let balance = native_balance(&app, &winner);
+  execute(&mut app, &winner, &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+  assert_eq!(native_balance(&app, &winner) - balance, POT);
+
+  execute(
+    &mut app,
+    "alice",
+    &lottery,
+    &ExecuteMsg::ClaimRefund { round: 1 },
+  )
+  .unwrap();
+  assert_eq!(native_balance(&app, lottery.as_str()), 0);
+}
@@ -0,0 +1,247 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{
+  to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, WasmMsg,
+};
+use cw_gelotto_lottery::{
+  models::round::{RandomnessSource, RoundStatus},
+  msg::ExecuteMsg,
+};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+/// Messages handled by the mock randomness proxy. The lottery sends it
+/// GetNextRandomness, and the test fulfills each request with Fulfill.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum ProxyMsg {
+  GetNextRandomness { job_id: String },
+  Fulfill { job_id: String, randomness: Binary },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum ProxyQueryMsg {
+  Requester { job_id: String },
+}
+
+const TIMEOUT_MINUTES: u32 = 60;
+
+// address of the contract that requested randomness for each job
+const REQUESTS: Map<String, Addr> = Map::new("requests");
+
+fn proxy_instantiate(
+  _deps: DepsMut,
+  _env: Env,
+  _info: MessageInfo,
+  _msg: Empty,
+) -> StdResult<Response> {
+  Ok(Response::new())
+}
+
+fn proxy_execute(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  msg: ProxyMsg,
+) -> StdResult<Response> {
+  match msg {
+    ProxyMsg::GetNextRandomness { job_id } => {
+      REQUESTS.save(deps.storage, job_id, &info.sender)?;
+      Ok(Response::new())
+    },
+    ProxyMsg::Fulfill { job_id, randomness } => {
+      let requester = REQUESTS.load(deps.storage, job_id.clone())?;
+      Ok(Response::new().add_message(WasmMsg::Execute {
+        contract_addr: requester.into(),
+        msg: to_binary(&ExecuteMsg::ReceiveRandomness { job_id, randomness })?,
+        funds: vec![],
+      }))
+    },
+  }
+}
+
+fn proxy_query(
+  deps: Deps,
+  _env: Env,
+  msg: ProxyQueryMsg,
+) -> StdResult<Binary> {
+  match msg {
+    ProxyQueryMsg::Requester { job_id } => to_binary(&REQUESTS.may_load(deps.storage, job_id)?),
+  }
+}
+
+fn proxy_contract() -> Box<dyn Contract<Empty>> {
+  Box::new(ContractWrapper::new(
+    proxy_execute,
+    proxy_instantiate,
+    proxy_query,
+  ))
+}
+
+/// Instantiate the mock proxy and a lottery whose rounds draw with randomness
+/// requested from it.
+fn setup(app: &mut App) -> (Addr, Addr) {
+  let code_id = app.store_code(proxy_contract());
+  let proxy = app
+    .instantiate_contract(
+      code_id,
+      Addr::unchecked(OWNER),
+      &Empty {},
+      &[],
+      "proxy",
+      None,
+    )
+    .unwrap();
+  let mut config = config(native_token());
+  config.randomness = Some(RandomnessSource::Proxy {
+    address: proxy.clone(),
+    timeout_minutes: TIMEOUT_MINUTES,
+  });
+  let lottery = instantiate_lottery(app, vec![config], 2);
+  (proxy, lottery)
+}
+
+fn requester(
+  app: &App,
+  proxy: &Addr,
+  job_id: &str,
+) -> Option<Addr> {
+  app
+    .wrap()
+    .query_wasm_smart(
+      proxy,
+      &ProxyQueryMsg::Requester {
+        job_id: job_id.to_owned(),
+      },
+    )
+    .unwrap()
+}
+
+fn fulfill(
+  app: &mut App,
+  proxy: &Addr,
+  job_id: &str,
+) -> anyhow::Result<cw_multi_test::AppResponse> {
+  app.execute_contract(
+    Addr::unchecked("relayer"),
+    proxy.clone(),
+    &ProxyMsg::Fulfill {
+      job_id: job_id.to_owned(),
+      randomness: Binary::from(vec![7u8; 32]),
+    },
+    &[],
+  )
+}
+
+#[test]
+fn closing_a_round_requests_randomness_from_the_proxy() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let (proxy, lottery) = setup(&mut app);
+
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+  assert_eq!(requester(&app, &proxy, "round-0"), None);
+
+  // the second wallet meets the round's target, closing it
+  buy_tickets(&mut app, &lottery, "bob", 2).unwrap();
+  let round = query_round(&app, &lottery, 0);
+  assert_eq!(round.status, RoundStatus::Closed);
+  assert!(round.closed_at.is_some());
+  assert_eq!(requester(&app, &proxy, "round-0"), Some(lottery.clone()));
+
+  // no more tickets can be bought while the round waits on its randomness
+  assert_eq!(
+    error_name(buy_tickets(&mut app, &lottery, "alice", 1).unwrap_err()),
+    "RoundClosed"
+  );
+}
+
+#[test]
+fn randomness_from_the_proxy_draws_the_round() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let (proxy, lottery) = setup(&mut app);
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+  buy_tickets(&mut app, &lottery, "bob", 2).unwrap();
+
+  fulfill(&mut app, &proxy, "round-0").unwrap();
+
+  let round = query_round(&app, &lottery, 0);
+  assert_eq!(round.status, RoundStatus::Complete);
+  let winners = round.winners.unwrap();
+  assert_eq!(winners.len(), 1);
+  assert!(winners[0].wallet == "alice" || winners[0].wallet == "bob");
+  assert_eq!(winners[0].amount_total.u128(), 3 * TICKET_PRICE);
+
+  // the next round is open for business
+  assert_eq!(query_round(&app, &lottery, 1).status, RoundStatus::Active);
+}
+
+#[test]
+fn randomness_from_another_sender_is_rejected() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let (_, lottery) = setup(&mut app);
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+  buy_tickets(&mut app, &lottery, "bob", 2).unwrap();
+
+  for sender in ["alice", OWNER] {
+    let err = execute(
+      &mut app,
+      sender,
+      &lottery,
+      &ExecuteMsg::ReceiveRandomness {
+        job_id: "round-0".to_owned(),
+        randomness: Binary::from(vec![7u8; 32]),
+      },
+    )
+    .unwrap_err();
+    assert_eq!(error_name(err), "NotAuthorized");
+  }
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Closed);
+}
+
+#[test]
+fn randomness_sent_twice_is_rejected() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let (proxy, lottery) = setup(&mut app);
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+  buy_tickets(&mut app, &lottery, "bob", 2).unwrap();
+
+  fulfill(&mut app, &proxy, "round-0").unwrap();
+  let winners = query_round(&app, &lottery, 0).winners;
+
+  let err = fulfill(&mut app, &proxy, "round-0").unwrap_err();
+  assert_eq!(error_name(err), "NotActive");
+  assert_eq!(query_round(&app, &lottery, 0).winners, winners);
+}
+
+#[test]
+fn players_are_refunded_when_randomness_times_out() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let (proxy, lottery) = setup(&mut app);
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+  buy_tickets(&mut app, &lottery, "bob", 2).unwrap();
+
+  // the round can't be terminated while the randomness may still arrive
+  advance_minutes(&mut app, TIMEOUT_MINUTES as u64);
+  assert_eq!(
+    error_name(execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap_err()),
+    "RoundNotReady"
+  );
+
+  // once it's late, the randomness is rejected and players are refunded
+  advance_minutes(&mut app, 1);
+  assert_eq!(
+    error_name(fulfill(&mut app, &proxy, "round-0").unwrap_err()),
+    "NotActive"
+  );
+  execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap();
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Canceled);
+  assert_eq!(query_round(&app, &lottery, 1).status, RoundStatus::Active);
+
+  for wallet in ["alice", "bob"] {
+    execute(&mut app, wallet, &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+    assert_eq!(native_balance(&app, wallet), INITIAL_BALANCE);
+  }
+}
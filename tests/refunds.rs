@@ -0,0 +1,155 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Addr;
+use cw_gelotto_lottery::{
+  models::{lottery::LotteryStatus, round::RoundStatus},
+  msg::ExecuteMsg,
+};
+use cw_multi_test::App;
+
+/// Instantiate a lottery whose rounds don't end before three wallets buy
+/// tickets, in which alice and bob have bought tickets in the first round.
+fn setup(app: &mut App) -> Addr {
+  let mut config = config(native_token());
+  config.targets.wallet_count = Some(3);
+  let lottery = instantiate_lottery(app, vec![config], 2);
+  buy_tickets(app, &lottery, "alice", 2).unwrap();
+  buy_tickets(app, &lottery, "bob", 1).unwrap();
+  lottery
+}
+
+fn claim_refund(
+  app: &mut App,
+  lottery: &Addr,
+  wallet: &str,
+) -> anyhow::Result<cw_multi_test::AppResponse> {
+  execute(app, wallet, lottery, &ExecuteMsg::ClaimRefund { round: 0 })
+}
+
+#[test]
+fn players_claim_refunds_after_the_round_is_canceled() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = setup(&mut app);
+
+  // no refunds before the round is canceled
+  assert_eq!(
+    error_name(claim_refund(&mut app, &lottery, "alice").unwrap_err()),
+    "NotCanceled"
+  );
+
+  execute(
+    &mut app,
+    OWNER,
+    &lottery,
+    &ExecuteMsg::CancelRound { index: 0 },
+  )
+  .unwrap();
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Canceled);
+  assert_eq!(query_round(&app, &lottery, 1).status, RoundStatus::Active);
+
+  claim_refund(&mut app, &lottery, "alice").unwrap();
+  assert_eq!(native_balance(&app, "alice"), INITIAL_BALANCE);
+  assert_eq!(native_balance(&app, lottery.as_str()), TICKET_PRICE);
+
+  // alice was removed from the round along with her tickets
+  let round = query_round(&app, &lottery, 0);
+  assert_eq!(round.counts.wallets, 1);
+  assert_eq!(round.counts.tickets, 1);
+
+  claim_refund(&mut app, &lottery, "bob").unwrap();
+  assert_eq!(native_balance(&app, "bob"), INITIAL_BALANCE);
+  assert_eq!(native_balance(&app, lottery.as_str()), 0);
+}
+
+#[test]
+fn a_refund_can_only_be_claimed_once() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = setup(&mut app);
+  execute(
+    &mut app,
+    OWNER,
+    &lottery,
+    &ExecuteMsg::CancelRound { index: 0 },
+  )
+  .unwrap();
+
+  claim_refund(&mut app, &lottery, "alice").unwrap();
+  assert_eq!(
+    error_name(claim_refund(&mut app, &lottery, "alice").unwrap_err()),
+    "PlayerNotFound"
+  );
+  assert_eq!(native_balance(&app, "alice"), INITIAL_BALANCE);
+
+  // nor can a wallet without tickets claim one
+  assert_eq!(
+    error_name(claim_refund(&mut app, &lottery, "mallory").unwrap_err()),
+    "PlayerNotFound"
+  );
+}
+
+#[test]
+fn issued_refunds_pay_the_recipient() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = setup(&mut app);
+  execute(
+    &mut app,
+    OWNER,
+    &lottery,
+    &ExecuteMsg::CancelRound { index: 0 },
+  )
+  .unwrap();
+
+  let issue_refund = ExecuteMsg::IssueRefund {
+    round: 0,
+    recipient: Addr::unchecked("bob"),
+  };
+
+  // only the owner or a refunder can issue refunds
+  assert_eq!(
+    error_name(execute(&mut app, "alice", &lottery, &issue_refund).unwrap_err()),
+    "NotAuthorized"
+  );
+
+  execute(&mut app, OWNER, &lottery, &issue_refund).unwrap();
+  assert_eq!(native_balance(&app, "bob"), INITIAL_BALANCE);
+  assert_eq!(native_balance(&app, OWNER), 0);
+
+  // bob's refund can't be issued or claimed again
+  assert_eq!(
+    error_name(execute(&mut app, OWNER, &lottery, &issue_refund).unwrap_err()),
+    "PlayerNotFound"
+  );
+  assert_eq!(
+    error_name(claim_refund(&mut app, &lottery, "bob").unwrap_err()),
+    "PlayerNotFound"
+  );
+}
+
+#[test]
+fn players_claim_refunds_after_the_lottery_is_canceled() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = setup(&mut app);
+
+  assert_eq!(
+    error_name(execute(&mut app, "alice", &lottery, &ExecuteMsg::CancelLottery {}).unwrap_err()),
+    "NotAuthorized"
+  );
+  execute(&mut app, OWNER, &lottery, &ExecuteMsg::CancelLottery {}).unwrap();
+  assert_eq!(
+    query_lottery(&app, &lottery).status,
+    LotteryStatus::Canceled
+  );
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Canceled);
+
+  claim_refund(&mut app, &lottery, "alice").unwrap();
+  claim_refund(&mut app, &lottery, "bob").unwrap();
+  assert_eq!(native_balance(&app, "alice"), INITIAL_BALANCE);
+  assert_eq!(native_balance(&app, "bob"), INITIAL_BALANCE);
+
+  // no more tickets can be bought
+  assert_eq!(
+    error_name(buy_tickets(&mut app, &lottery, "alice", 1).unwrap_err()),
+    "NotActive"
+  );
+}
@@ -0,0 +1,165 @@
+mod common;
+
+use common::{token, *};
+use cosmwasm_std::Addr;
+use cw_gelotto_lottery::{
+  models::round::{Config, RoundStatus, RoyaltyRecipient, Token},
+  msg::ExecuteMsg,
+};
+use cw_multi_test::{App, AppResponse};
+
+// carol's royalty is sent when the round is drawn, while dave claims his
+const CAROL_PCT: u8 = 10;
+const DAVE_PCT: u8 = 5;
+
+fn with_royalties(mut config: Config) -> Config {
+  config.royalties = vec![
+    RoyaltyRecipient {
+      address: Addr::unchecked("carol"),
+      autosend: Some(true),
+      pct: CAROL_PCT,
+    },
+    RoyaltyRecipient {
+      address: Addr::unchecked("dave"),
+      autosend: None,
+      pct: DAVE_PCT,
+    },
+  ];
+  config
+}
+
+fn pct_of(
+  amount: u128,
+  pct: u8,
+) -> u128 {
+  amount * pct as u128 / 100
+}
+
+fn buy_cw20_tickets(
+  app: &mut App,
+  lottery: &Addr,
+  wallet: &str,
+  count: u32,
+) -> anyhow::Result<AppResponse> {
+  execute(
+    app,
+    wallet,
+    lottery,
+    &ExecuteMsg::BuyTickets {
+      count,
+      message: None,
+      is_public: None,
+    },
+  )
+}
+
+fn winner(
+  app: &App,
+  lottery: &Addr,
+) -> String {
+  let round = query_round(app, lottery, 0);
+  assert_eq!(round.status, RoundStatus::Complete);
+  round.winners.unwrap()[0].wallet.to_string()
+}
+
+#[test]
+fn native_royalties_are_paid_from_the_pot_when_tickets_end_the_round() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = instantiate_lottery(&mut app, vec![with_royalties(config(native_token()))], 2);
+
+  // bob's order of 1 ticket ends the round with a pot of 5 tickets
+  buy_tickets(&mut app, &lottery, "alice", 4).unwrap();
+  buy_tickets(&mut app, &lottery, "bob", 1).unwrap();
+  let pot = 5 * TICKET_PRICE;
+
+  // carol is paid her pct of the whole pot, not of bob's order, out of the
+  // lottery's balance
+  assert_eq!(native_balance(&app, "carol"), pct_of(pot, CAROL_PCT));
+  assert_eq!(
+    native_balance(&app, lottery.as_str()),
+    pot - pct_of(pot, CAROL_PCT)
+  );
+  assert_eq!(native_balance(&app, "bob"), INITIAL_BALANCE - TICKET_PRICE);
+
+  // dave claims his royalty, and the winner claims the rest
+  assert_eq!(native_balance(&app, "dave"), 0);
+  execute(&mut app, "dave", &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+  assert_eq!(native_balance(&app, "dave"), pct_of(pot, DAVE_PCT));
+
+  let winner = winner(&app, &lottery);
+  let balance_before_claim = native_balance(&app, &winner);
+  execute(&mut app, &winner, &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+  assert_eq!(
+    native_balance(&app, &winner) - balance_before_claim,
+    pot - pct_of(pot, CAROL_PCT) - pct_of(pot, DAVE_PCT)
+  );
+  assert_eq!(native_balance(&app, lottery.as_str()), 0);
+}
+
+#[test]
+fn native_royalties_are_paid_from_the_pot_when_terminated() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let mut config = with_royalties(config(native_token()));
+  config.targets.wallet_count = None;
+  config.targets.duration_minutes = Some(10);
+  let lottery = instantiate_lottery(&mut app, vec![config], 2);
+
+  buy_tickets(&mut app, &lottery, "alice", 2).unwrap();
+  buy_tickets(&mut app, &lottery, "bob", 3).unwrap();
+  let pot = 5 * TICKET_PRICE;
+  assert_eq!(native_balance(&app, "carol"), 0);
+
+  app.update_block(|block| {
+    block.time = block.time.plus_seconds(10 * 60);
+    block.height += 1;
+  });
+  execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap();
+
+  assert_eq!(native_balance(&app, "carol"), pct_of(pot, CAROL_PCT));
+  assert_eq!(
+    native_balance(&app, lottery.as_str()),
+    pot - pct_of(pot, CAROL_PCT)
+  );
+  execute(&mut app, "dave", &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+  assert_eq!(native_balance(&app, "dave"), pct_of(pot, DAVE_PCT));
+}
+
+#[test]
+fn cw20_royalties_are_paid_from_the_pot() {
+  let mut app = mock_app(&[]);
+  let token = token::instantiate_token(&mut app, &["alice", "bob"]);
+  let config = with_royalties(config(Token::Cw20 {
+    address: token.clone(),
+  }));
+  let lottery = instantiate_lottery(&mut app, vec![config], 2);
+
+  buy_cw20_tickets(&mut app, &lottery, "alice", 4).unwrap();
+  buy_cw20_tickets(&mut app, &lottery, "bob", 1).unwrap();
+  let pot = 5 * TICKET_PRICE;
+
+  // each buyer paid only for their own order, and carol was paid her pct of
+  // the pot out of the lottery's balance
+  assert_eq!(
+    token::balance(&app, &token, "alice"),
+    INITIAL_BALANCE - 4 * TICKET_PRICE
+  );
+  assert_eq!(
+    token::balance(&app, &token, "bob"),
+    INITIAL_BALANCE - TICKET_PRICE
+  );
+  assert_eq!(
+    token::balance(&app, &token, "carol"),
+    pct_of(pot, CAROL_PCT)
+  );
+  assert_eq!(
+    token::balance(&app, &token, lottery.as_str()),
+    pot - pct_of(pot, CAROL_PCT)
+  );
+
+  execute(&mut app, "dave", &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+  assert_eq!(token::balance(&app, &token, "dave"), pct_of(pot, DAVE_PCT));
+
+  let winner = winner(&app, &lottery);
+  execute(&mut app, &winner, &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+  assert_eq!(token::balance(&app, &token, lottery.as_str()), 0);
+}
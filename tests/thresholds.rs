@@ -0,0 +1,172 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Addr;
+use cw_gelotto_lottery::{
+  models::{
+    lottery::LotteryStatus,
+    round::{Config, RoundStatus, ThresholdPolicy, Token},
+  },
+  msg::ExecuteMsg,
+};
+use cw_multi_test::App;
+
+const DURATION_MINUTES: u32 = 10;
+
+/// Return a config for rounds that end after their duration, which need two
+/// wallets to be drawn.
+fn timed_config(policy: ThresholdPolicy) -> Config {
+  let mut config = config(native_token());
+  config.targets.wallet_count = None;
+  config.targets.duration_minutes = Some(DURATION_MINUTES);
+  config.threshold_policy = Some(policy);
+  config
+}
+
+fn terminate_round(
+  app: &mut App,
+  lottery: &Addr,
+) -> anyhow::Result<cw_multi_test::AppResponse> {
+  advance_minutes(app, DURATION_MINUTES as u64);
+  execute(app, "anyone", lottery, &ExecuteMsg::TerminateRound {})
+}
+
+fn claim_refund(
+  app: &mut App,
+  lottery: &Addr,
+  wallet: &str,
+  round: u32,
+) -> anyhow::Result<cw_multi_test::AppResponse> {
+  execute(app, wallet, lottery, &ExecuteMsg::ClaimRefund { round })
+}
+
+#[test]
+fn canceling_the_lottery_refunds_the_carryover_to_its_rounds() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = instantiate_lottery(&mut app, vec![timed_config(ThresholdPolicy::Rollover)], 3);
+
+  // alice plays rounds 0 and 1 alone, so both roll over into round 2
+  buy_tickets(&mut app, &lottery, "alice", 3).unwrap();
+  terminate_round(&mut app, &lottery).unwrap();
+  buy_tickets(&mut app, &lottery, "alice", 2).unwrap();
+  terminate_round(&mut app, &lottery).unwrap();
+  buy_tickets(&mut app, &lottery, "bob", 1).unwrap();
+
+  execute(&mut app, OWNER, &lottery, &ExecuteMsg::CancelLottery {}).unwrap();
+  assert_eq!(
+    query_lottery(&app, &lottery).status,
+    LotteryStatus::Canceled
+  );
+
+  // each round can be refunded on its own
+  for round in 0..3 {
+    assert_eq!(
+      query_round(&app, &lottery, round).status,
+      RoundStatus::Canceled
+    );
+  }
+  claim_refund(&mut app, &lottery, "alice", 0).unwrap();
+  claim_refund(&mut app, &lottery, "alice", 1).unwrap();
+  claim_refund(&mut app, &lottery, "bob", 2).unwrap();
+  assert_eq!(native_balance(&app, "alice"), INITIAL_BALANCE);
+  assert_eq!(native_balance(&app, "bob"), INITIAL_BALANCE);
+  assert_eq!(native_balance(&app, lottery.as_str()), 0);
+}
+
+#[test]
+fn extend_keeps_the_round_open_for_more_players() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = instantiate_lottery(&mut app, vec![timed_config(ThresholdPolicy::Extend)], 2);
+
+  buy_tickets(&mut app, &lottery, "alice", 3).unwrap();
+  terminate_round(&mut app, &lottery).unwrap();
+  let round = query_round(&app, &lottery, 0);
+  assert_eq!(round.status, RoundStatus::Active);
+  assert_eq!(round.started_at, Some(app.block_info().time));
+
+  // once bob joins, the round is drawn when its restarted duration elapses
+  buy_tickets(&mut app, &lottery, "bob", 1).unwrap();
+  terminate_round(&mut app, &lottery).unwrap();
+  let round = query_round(&app, &lottery, 0);
+  assert_eq!(round.status, RoundStatus::Complete);
+  assert_eq!(
+    round.winners.unwrap()[0].amount_total.u128(),
+    4 * TICKET_PRICE
+  );
+}
+
+#[test]
+fn rollover_carries_the_pot_into_the_next_round() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let lottery = instantiate_lottery(&mut app, vec![timed_config(ThresholdPolicy::Rollover)], 2);
+
+  buy_tickets(&mut app, &lottery, "alice", 3).unwrap();
+  terminate_round(&mut app, &lottery).unwrap();
+  let round = query_round(&app, &lottery, 0);
+  assert_eq!(round.status, RoundStatus::Complete);
+  assert_eq!(round.winners, Some(vec![]));
+  assert_eq!(
+    query_round(&app, &lottery, 1).carryover.u128(),
+    3 * TICKET_PRICE
+  );
+
+  // the winner of the next round wins the carryover as well
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+  buy_tickets(&mut app, &lottery, "bob", 1).unwrap();
+  terminate_round(&mut app, &lottery).unwrap();
+  let round = query_round(&app, &lottery, 1);
+  assert_eq!(round.status, RoundStatus::Complete);
+  assert_eq!(
+    round.winners.unwrap()[0].amount_total.u128(),
+    5 * TICKET_PRICE
+  );
+}
+
+#[test]
+fn refund_cancels_the_round_and_credits_its_players() {
+  let mut app = mock_app(&["alice"]);
+  let lottery = instantiate_lottery(&mut app, vec![timed_config(ThresholdPolicy::Refund)], 2);
+
+  buy_tickets(&mut app, &lottery, "alice", 3).unwrap();
+  terminate_round(&mut app, &lottery).unwrap();
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Canceled);
+  assert_eq!(query_round(&app, &lottery, 1).status, RoundStatus::Active);
+
+  execute(&mut app, "alice", &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+  assert_eq!(native_balance(&app, "alice"), INITIAL_BALANCE);
+}
+
+#[test]
+fn rollover_falls_back_to_refund_without_a_next_round_of_the_same_token() {
+  let mut app = mock_app(&["alice"]);
+  let mut other_token_config = timed_config(ThresholdPolicy::Rollover);
+  other_token_config.token = Token::Native {
+    denom: "uother".to_owned(),
+  };
+  let configs = vec![timed_config(ThresholdPolicy::Rollover), other_token_config];
+  let lottery = instantiate_lottery(&mut app, configs, 2);
+
+  // round 1 is paid for in another token, so round 0's pot can't move into it
+  buy_tickets(&mut app, &lottery, "alice", 3).unwrap();
+  terminate_round(&mut app, &lottery).unwrap();
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Canceled);
+  assert!(query_round(&app, &lottery, 1).carryover.is_zero());
+  execute(&mut app, "alice", &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+  assert_eq!(native_balance(&app, "alice"), INITIAL_BALANCE);
+}
+
+#[test]
+fn rollover_falls_back_to_refund_in_the_final_round() {
+  let mut app = mock_app(&["alice"]);
+  let lottery = instantiate_lottery(&mut app, vec![timed_config(ThresholdPolicy::Rollover)], 1);
+
+  buy_tickets(&mut app, &lottery, "alice", 3).unwrap();
+  terminate_round(&mut app, &lottery).unwrap();
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Canceled);
+  assert_eq!(
+    query_lottery(&app, &lottery).status,
+    LotteryStatus::Complete
+  );
+  execute(&mut app, "alice", &lottery, &ExecuteMsg::ClaimRewards {}).unwrap();
+  assert_eq!(native_balance(&app, "alice"), INITIAL_BALANCE);
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use common::*;
+use cw_gelotto_lottery::{models::round::RoundStatus, msg::ExecuteMsg};
+
+#[test]
+fn terminating_an_expired_round_without_players_restarts_it() {
+  let mut app = mock_app(&["alice", "bob"]);
+  let mut config = config(native_token());
+  config.targets.wallet_count = None;
+  config.targets.duration_minutes = Some(10);
+  let lottery = instantiate_lottery(&mut app, vec![config], 2);
+
+  advance_minutes(&mut app, 10);
+  execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap();
+
+  // the round's duration starts over from when it was terminated
+  let round = query_round(&app, &lottery, 0);
+  assert_eq!(round.status, RoundStatus::Active);
+  assert_eq!(round.started_at, Some(app.block_info().time));
+  assert_eq!(query_lottery(&app, &lottery).rounds.index, 0);
+  assert_eq!(
+    error_name(execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap_err()),
+    "RoundNotReady"
+  );
+
+  // and it ends once its new duration has elapsed
+  buy_tickets(&mut app, &lottery, "alice", 1).unwrap();
+  buy_tickets(&mut app, &lottery, "bob", 1).unwrap();
+  advance_minutes(&mut app, 10);
+  execute(&mut app, "anyone", &lottery, &ExecuteMsg::TerminateRound {}).unwrap();
+  assert_eq!(query_round(&app, &lottery, 0).status, RoundStatus::Complete);
+}